@@ -1,3 +1,5 @@
+#![allow(clippy::large_const_arrays)]
+
 pub mod constants;
 pub mod heads_up;
 pub mod lookup;
@@ -1,23 +1,24 @@
 use assets::constants::*;
-use criterion::{criterion_group, criterion_main, Criterion};
-use holdem_hand_evaluator::Hand;
+use criterion::{black_box, criterion_group, criterion_main, Bencher, Criterion};
+use holdem_hand_evaluator::{evaluate_five, evaluate_simd_x8, Card, Hand};
+use std::convert::TryInto;
 
 fn bench() -> u16 {
     let mut sum: u16 = 0;
     for i in 0..(NUMBER_OF_CARDS - 6) {
-        let hand = Hand::new().add_card(i);
+        let hand = unsafe { Hand::new().add_card_unchecked(i) };
         for j in (i + 1)..(NUMBER_OF_CARDS - 5) {
-            let hand = hand.add_card(j);
+            let hand = unsafe { hand.add_card_unchecked(j) };
             for k in (j + 1)..(NUMBER_OF_CARDS - 4) {
-                let hand = hand.add_card(k);
+                let hand = unsafe { hand.add_card_unchecked(k) };
                 for m in (k + 1)..(NUMBER_OF_CARDS - 3) {
-                    let hand = hand.add_card(m);
+                    let hand = unsafe { hand.add_card_unchecked(m) };
                     for n in (m + 1)..(NUMBER_OF_CARDS - 2) {
-                        let hand = hand.add_card(n);
+                        let hand = unsafe { hand.add_card_unchecked(n) };
                         for p in (n + 1)..(NUMBER_OF_CARDS - 1) {
-                            let hand = hand.add_card(p);
+                            let hand = unsafe { hand.add_card_unchecked(p) };
                             for q in (p + 1)..NUMBER_OF_CARDS {
-                                let hand = hand.add_card(q);
+                                let hand = unsafe { hand.add_card_unchecked(q) };
                                 let rank = hand.evaluate();
                                 sum = sum.wrapping_add(rank);
                             }
@@ -30,9 +31,204 @@ fn bench() -> u16 {
     sum
 }
 
+/// Samples 8,111 5-card hands in a fixed order by striding over the card indices.
+fn sample_five_card_hands() -> Vec<Hand> {
+    let mut hands = Vec::new();
+    for i in (0..NUMBER_OF_CARDS).step_by(3) {
+        let hand = unsafe { Hand::new().add_card_unchecked(i) };
+        for j in ((i + 1)..NUMBER_OF_CARDS).step_by(5) {
+            let hand = unsafe { hand.add_card_unchecked(j) };
+            for k in ((j + 1)..NUMBER_OF_CARDS).step_by(7) {
+                let hand = unsafe { hand.add_card_unchecked(k) };
+                for m in ((k + 1)..NUMBER_OF_CARDS).step_by(3) {
+                    let hand = unsafe { hand.add_card_unchecked(m) };
+                    for n in ((m + 1)..NUMBER_OF_CARDS).step_by(2) {
+                        hands.push(unsafe { hand.add_card_unchecked(n) });
+                    }
+                }
+            }
+        }
+    }
+    hands
+}
+
 fn criterion_bench(c: &mut Criterion) {
-    c.bench_function("evaluate_hand (133,784,560 hands)", |b| b.iter(|| bench()));
+    c.bench_function("evaluate_hand (133,784,560 hands)", |b| b.iter(bench));
+}
+
+fn criterion_five(c: &mut Criterion) {
+    let fives = sample_five_card_hands()
+        .iter()
+        .map(|hand| hand.cards().collect::<Vec<_>>().try_into().unwrap())
+        .collect::<Vec<[Card; 5]>>();
+
+    let mut group = c.benchmark_group(format!("5-card evaluation ({} hands)", fives.len()));
+    group.bench_function("Hand::evaluate()", |b| {
+        b.iter(|| {
+            let mut sum: u16 = 0;
+            for five in black_box(&fives) {
+                let hand = five.iter().fold(Hand::new(), |hand, c| hand.add_card(*c));
+                sum = sum.wrapping_add(hand.evaluate());
+            }
+            sum
+        })
+    });
+    group.bench_function("evaluate_five()", |b| {
+        b.iter(|| {
+            let mut sum: u16 = 0;
+            for five in black_box(&fives) {
+                let rank = evaluate_five(five[0], five[1], five[2], five[3], five[4]);
+                sum = sum.wrapping_add(rank);
+            }
+            sum
+        })
+    });
+    group.finish();
+}
+
+fn criterion_construction(c: &mut Criterion) {
+    let mut group = c.benchmark_group("construction");
+    group.bench_function("Hand::new()", |b| b.iter(|| black_box(Hand::new())));
+    group.bench_function("Hand::with_cards(7)", |b| {
+        b.iter(|| black_box(Hand::with_cards(black_box(7))))
+    });
+    group.finish();
+}
+
+fn criterion_outs(c: &mut Criterion) {
+    let hero = "4s3s".parse::<Hand>().unwrap();
+    let villain = "9h8d".parse::<Hand>().unwrap();
+    let board = "QsTh7sJc".parse::<Hand>().unwrap();
+    let mut group = c.benchmark_group("outs (flush draw vs. straight on the turn)");
+    group.bench_function("outs().len()", |b| {
+        b.iter(|| {
+            black_box(hero)
+                .outs(black_box(villain), black_box(board))
+                .len()
+        })
+    });
+    group.bench_function("count_outs()", |b| {
+        b.iter(|| black_box(hero).count_outs(black_box(villain), black_box(board)))
+    });
+    group.finish();
+}
+
+fn criterion_simd(c: &mut Criterion) {
+    let mut hands = sample_five_card_hands();
+    hands.truncate(hands.len() / 8 * 8);
+
+    let mut group = c.benchmark_group(format!("8-way evaluation ({} hands)", hands.len()));
+    group.bench_function("Hand::evaluate()", |b| {
+        b.iter(|| {
+            let mut sum: u16 = 0;
+            for hand in black_box(&hands) {
+                sum = sum.wrapping_add(hand.evaluate());
+            }
+            sum
+        })
+    });
+    group.bench_function("evaluate_simd_x8()", |b| {
+        b.iter(|| {
+            let mut sum: u16 = 0;
+            for chunk in black_box(&hands).chunks_exact(8) {
+                let ranks = evaluate_simd_x8(chunk.try_into().unwrap());
+                sum = ranks.iter().fold(sum, |sum, rank| sum.wrapping_add(*rank));
+            }
+            sum
+        })
+    });
+    group.finish();
+}
+
+fn criterion_predicates(c: &mut Criterion) {
+    let hands = sample_five_card_hands();
+
+    let mut group = c.benchmark_group(format!("category predicates ({} hands)", hands.len()));
+    group.bench_function("Hand::evaluate()", |b| {
+        b.iter(|| {
+            let mut sum: u16 = 0;
+            for hand in black_box(&hands) {
+                sum = sum.wrapping_add(hand.evaluate());
+            }
+            sum
+        })
+    });
+    fn bench_predicate(b: &mut Bencher, hands: &[Hand], predicate: impl Fn(&Hand) -> bool) {
+        b.iter(|| {
+            let mut count: u16 = 0;
+            for hand in black_box(hands) {
+                count = count.wrapping_add(predicate(hand) as u16);
+            }
+            count
+        })
+    }
+    group.bench_function("has_pair()", |b| bench_predicate(b, &hands, Hand::has_pair));
+    group.bench_function("has_two_pair()", |b| {
+        bench_predicate(b, &hands, Hand::has_two_pair)
+    });
+    group.bench_function("has_trips()", |b| {
+        bench_predicate(b, &hands, Hand::has_trips)
+    });
+    group.bench_function("has_quads()", |b| {
+        bench_predicate(b, &hands, Hand::has_quads)
+    });
+    group.bench_function("has_straight()", |b| {
+        bench_predicate(b, &hands, Hand::has_straight)
+    });
+    group.bench_function("has_flush()", |b| {
+        bench_predicate(b, &hands, Hand::has_flush)
+    });
+    group.finish();
+}
+
+#[cfg(feature = "rayon")]
+fn criterion_batch(c: &mut Criterion) {
+    use holdem_hand_evaluator::evaluate_batch;
+
+    let mut hands = Vec::new();
+    for i in 0..(NUMBER_OF_CARDS - 4) {
+        let hand = unsafe { Hand::new().add_card_unchecked(i) };
+        for j in (i + 1)..(NUMBER_OF_CARDS - 3) {
+            let hand = unsafe { hand.add_card_unchecked(j) };
+            for k in (j + 1)..(NUMBER_OF_CARDS - 2) {
+                let hand = unsafe { hand.add_card_unchecked(k) };
+                for m in (k + 1)..(NUMBER_OF_CARDS - 1) {
+                    let hand = unsafe { hand.add_card_unchecked(m) };
+                    for n in (m + 1)..NUMBER_OF_CARDS {
+                        hands.push(unsafe { hand.add_card_unchecked(n) });
+                    }
+                }
+            }
+        }
+    }
+
+    let mut group = c.benchmark_group("batch (2,598,960 hands)");
+    group.bench_function("sequential", |b| {
+        b.iter(|| hands.iter().map(|hand| hand.evaluate()).collect::<Vec<_>>())
+    });
+    group.bench_function("evaluate_batch", |b| b.iter(|| evaluate_batch(&hands)));
+    group.finish();
 }
 
-criterion_group!(benches, criterion_bench);
+#[cfg(feature = "rayon")]
+criterion_group!(
+    benches,
+    criterion_bench,
+    criterion_construction,
+    criterion_five,
+    criterion_outs,
+    criterion_simd,
+    criterion_predicates,
+    criterion_batch
+);
+#[cfg(not(feature = "rayon"))]
+criterion_group!(
+    benches,
+    criterion_bench,
+    criterion_construction,
+    criterion_five,
+    criterion_outs,
+    criterion_simd,
+    criterion_predicates
+);
 criterion_main!(benches);
@@ -0,0 +1,28 @@
+#![no_main]
+use holdem_hand_evaluator::Hand;
+use libfuzzer_sys::fuzz_target;
+use std::convert::TryInto;
+
+// data[0..8] and data[8..16] are interpreted directly as the little-endian `key` and `mask` of a
+// hand, so arbitrary (mostly inconsistent) pairs reach `is_valid()`, which must reject them.
+fuzz_target!(|data: [u8; 16]| {
+    let key = u64::from_le_bytes(data[..8].try_into().unwrap());
+    let mask = u64::from_le_bytes(data[8..].try_into().unwrap());
+    let hand = Hand::from_raw_parts(key, mask);
+
+    if !hand.is_valid() {
+        return;
+    }
+    assert!(hand.len() <= 7);
+    assert_eq!(Hand::from_internal_mask(mask), Ok(hand));
+    if (5..=7).contains(&hand.len()) {
+        let rank = hand.evaluate();
+        assert!(rank >> 12 <= 8);
+        assert_eq!(
+            rank,
+            hand.cards()
+                .fold(Hand::new(), |h, c| h.add_card(c))
+                .evaluate()
+        );
+    }
+});
@@ -4,6 +4,8 @@
 // reference: Z. J. Czech, G. Havas, and B. S. Majewski. "Perfect hashing".
 //            Theoretical Computer Science, 182(1-2), 1-143. 1997. (Section 5.2)
 
+#![allow(clippy::needless_range_loop)]
+
 use assets::constants::*;
 use std::cmp::max;
 use std::fs::File;
@@ -1,5 +1,7 @@
 // generate lookup tables.
 
+#![allow(clippy::needless_range_loop)]
+
 mod kev;
 
 use assets::constants::*;
@@ -41,17 +43,15 @@ fn update(
     let is_flush = key & FLUSH_MASK;
     if is_flush > 0 {
         let flush_key = (mask >> (4 * is_flush.leading_zeros())) as u16;
-        match lookup_flush.insert(flush_key as usize, val) {
-            Some(v) => assert_eq!(val, v),
-            None => (),
-        };
+        if let Some(v) = lookup_flush.insert(flush_key as usize, val) {
+            assert_eq!(val, v);
+        }
     } else {
         let mixed_key = key as u32 as usize;
         let offset = OFFSETS[mixed_key >> OFFSET_SHIFT] as usize;
         let hash_key = mixed_key.wrapping_add(offset);
-        match lookup.insert(hash_key, val) {
-            Some(v) => assert_eq!(val, v),
-            None => (),
+        if let Some(v) = lookup.insert(hash_key, val) {
+            assert_eq!(val, v);
         }
     }
 }
@@ -0,0 +1,24 @@
+use crate::hand::*;
+use rayon::prelude::*;
+
+/// Evaluates `hands` in parallel and returns their hand strengths in the same order.
+/// Every hand must satisfy the same conditions as `Hand::evaluate()`.
+pub fn evaluate_batch(hands: &[Hand]) -> Vec<u16> {
+    hands.par_iter().map(Hand::evaluate).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::*;
+
+    #[test]
+    fn test_evaluate_batch() {
+        assert!(evaluate_batch(&[]).is_empty());
+
+        let mut rng = Xorshift64::new(0x2545_f491_4f6c_dd1d);
+        let hands = (0..100_000).map(|_| rng.deal_hand(7)).collect::<Vec<_>>();
+        let expected = hands.iter().map(|hand| hand.evaluate()).collect::<Vec<_>>();
+        assert_eq!(evaluate_batch(&hands), expected);
+    }
+}
@@ -0,0 +1,176 @@
+use crate::card::*;
+use crate::hand::*;
+use std::convert::TryFrom;
+use std::fmt;
+use std::ops::Deref;
+
+/// Error type for constructing a `Board`.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum BoardError {
+    /// the resulting number of cards is not 0, 3, 4 or 5
+    InvalidLength(usize),
+    /// a card is dealt to a board of `len` cards, while the street requires `expected` cards
+    WrongStreet { len: usize, expected: usize },
+    /// a card is dealt twice
+    DuplicateCard(Card),
+}
+
+impl fmt::Display for BoardError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BoardError::InvalidLength(len) => {
+                write!(f, "invalid board length: {} (must be 0, 3, 4 or 5)", len)
+            }
+            BoardError::WrongStreet { len, expected } => write!(
+                f,
+                "wrong street: board has {} cards (must be {})",
+                len, expected
+            ),
+            BoardError::DuplicateCard(card) => write!(f, "duplicate card: {}", card),
+        }
+    }
+}
+
+impl std::error::Error for BoardError {}
+
+/// A Hold'em board, guaranteed to consist of 0 (preflop), 3 (flop), 4 (turn) or 5 (river) cards.
+/// Dereferences to `Hand`.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Board {
+    hand: Hand,
+}
+
+impl Board {
+    /// Creates a new empty board.
+    #[inline]
+    pub fn preflop() -> Self {
+        Self { hand: Hand::new() }
+    }
+
+    /// Creates a new board consisting of the flop cards `c0`, `c1` and `c2`.
+    #[inline]
+    pub fn flop(c0: Card, c1: Card, c2: Card) -> Result<Self, BoardError> {
+        let hand = [c0, c1, c2]
+            .iter()
+            .try_fold(Hand::new(), |hand, card| add_card(hand, *card))?;
+        Ok(Self { hand })
+    }
+
+    /// Returns a new board where the turn card `card` is added to the flop `self`.
+    #[inline]
+    pub fn turn(&self, card: Card) -> Result<Self, BoardError> {
+        self.deal(card, 3)
+    }
+
+    /// Returns a new board where the river card `card` is added to the turn `self`.
+    #[inline]
+    pub fn river(&self, card: Card) -> Result<Self, BoardError> {
+        self.deal(card, 4)
+    }
+
+    /// Returns the hand consisting of the same cards as `self`.
+    #[inline]
+    pub fn to_hand(&self) -> Hand {
+        self.hand
+    }
+
+    fn deal(&self, card: Card, expected_len: usize) -> Result<Self, BoardError> {
+        if self.hand.len() != expected_len {
+            return Err(BoardError::WrongStreet {
+                len: self.hand.len(),
+                expected: expected_len,
+            });
+        }
+        Ok(Self {
+            hand: add_card(self.hand, card)?,
+        })
+    }
+}
+
+fn add_card(hand: Hand, card: Card) -> Result<Hand, BoardError> {
+    if hand.contains(card) {
+        Err(BoardError::DuplicateCard(card))
+    } else {
+        Ok(hand.add_card(card))
+    }
+}
+
+impl Deref for Board {
+    type Target = Hand;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.hand
+    }
+}
+
+/// Fails when `hand` does not consist of 0, 3, 4 or 5 cards.
+impl TryFrom<Hand> for Board {
+    type Error = BoardError;
+
+    #[inline]
+    fn try_from(hand: Hand) -> Result<Self, Self::Error> {
+        match hand.len() {
+            0 | 3 | 4 | 5 => Ok(Self { hand }),
+            len => Err(BoardError::InvalidLength(len)),
+        }
+    }
+}
+
+impl fmt::Display for Board {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.hand.fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn card(card_str: &str) -> Card {
+        card_str.parse().unwrap()
+    }
+
+    #[test]
+    fn test_streets() {
+        assert!(Board::preflop().is_empty());
+        let flop = Board::flop(card("As"), card("Kd"), card("7h")).unwrap();
+        assert_eq!(flop.len(), 3);
+        let turn = flop.turn(card("2c")).unwrap();
+        assert_eq!(turn.len(), 4);
+        let river = turn.river(card("9s")).unwrap();
+        assert_eq!(river.to_hand(), "AsKd7h2c9s".parse().unwrap());
+        assert!(river.evaluate() > 0);
+        assert_eq!(river.to_string(), river.to_hand().to_string());
+
+        let wrong_street = |len, expected| Err(BoardError::WrongStreet { len, expected });
+        assert_eq!(river.river(card("3s")), wrong_street(5, 4));
+        assert_eq!(turn.turn(card("3s")), wrong_street(4, 3));
+        assert_eq!(flop.river(card("3s")), wrong_street(3, 4));
+        assert_eq!(Board::preflop().turn(card("3s")), wrong_street(0, 3));
+        assert_eq!(
+            flop.river(card("3s")).unwrap_err().to_string(),
+            "wrong street: board has 3 cards (must be 4)"
+        );
+        assert_eq!(
+            Board::flop(card("As"), card("Kd"), card("As")),
+            Err(BoardError::DuplicateCard(card("As")))
+        );
+        assert_eq!(
+            flop.turn(card("Kd")),
+            Err(BoardError::DuplicateCard(card("Kd")))
+        );
+    }
+
+    #[test]
+    fn test_try_from_hand() {
+        for len in 0..=7 {
+            let hand = "AsKdQhJcTs9h8c".parse::<Hand>().unwrap();
+            let hand = Hand::from_slice(&hand.cards().take(len).collect::<Vec<_>>());
+            match len {
+                0 | 3 | 4 | 5 => assert_eq!(Board::try_from(hand).unwrap().to_hand(), hand),
+                _ => assert_eq!(Board::try_from(hand), Err(BoardError::InvalidLength(len))),
+            }
+        }
+    }
+}
@@ -0,0 +1,328 @@
+use assets::constants::*;
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+
+const RANK_STRS: [&str; NUMBER_OF_RANKS] = [
+    "2", "3", "4", "5", "6", "7", "8", "9", "T", "J", "Q", "K", "A",
+];
+
+const SUIT_STRS: [&str; 4] = ["s", "h", "c", "d"];
+
+/// Returns the character of `rank` (0-12) as a string, e.g., "2" for 0 and "A" for 12.
+/// This function panics when `rank` is out of range.
+#[inline]
+pub const fn rank_to_string(rank: u8) -> &'static str {
+    RANK_STRS[rank as usize]
+}
+
+/// Returns the character of `suit` (0-3) as a string, i.e., "s", "h", "c" or "d".
+/// This function panics when `suit` is out of range.
+#[inline]
+pub const fn suit_to_string(suit: u8) -> &'static str {
+    SUIT_STRS[suit as usize]
+}
+
+/// Returns the card index `4 * rank + suit` of the card of `rank` (0 corresponds to the deuce,
+/// and 12 corresponds to the ace) and `suit` (0-3 correspond to spades, hearts, clubs and
+/// diamonds). This encoding is shared by `Card::index()` and the `usize` arguments of `Hand`.
+/// This function panics in debug builds when `rank` or `suit` is out of range.
+#[inline]
+pub fn card_index(rank: u8, suit: u8) -> usize {
+    debug_assert!((rank as usize) < NUMBER_OF_RANKS && suit < 4);
+    4 * rank as usize + suit as usize
+}
+
+/// Returns the rank of the card index `card`, i.e., `card / 4` (the inverse of `card_index()`).
+/// This function panics in debug builds when `card` is out of range.
+#[inline]
+pub fn card_rank(card: usize) -> u8 {
+    debug_assert!(card < NUMBER_OF_CARDS);
+    (card / 4) as u8
+}
+
+/// Returns the suit of the card index `card`, i.e., `card % 4` (the inverse of `card_index()`).
+/// This function panics in debug builds when `card` is out of range.
+#[inline]
+pub fn card_suit(card: usize) -> u8 {
+    debug_assert!(card < NUMBER_OF_CARDS);
+    (card % 4) as u8
+}
+
+/// Error type for constructing a `Card` from out-of-range values.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum CardError {
+    /// rank is not in the range \[0, 12\]
+    InvalidRank(u8),
+    /// suit is not in the range \[0, 3\]
+    InvalidSuit(u8),
+    /// card index is not in the range \[0, 51\]
+    InvalidIndex(usize),
+}
+
+impl fmt::Display for CardError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CardError::InvalidRank(rank) => write!(f, "invalid rank: {} (must be 0-12)", rank),
+            CardError::InvalidSuit(suit) => write!(f, "invalid suit: {} (must be 0-3)", suit),
+            CardError::InvalidIndex(index) => {
+                write!(f, "invalid card index: {} (must be 0-51)", index)
+            }
+        }
+    }
+}
+
+impl std::error::Error for CardError {}
+
+/// A playing card, guaranteed to be in the range \[0, 51\].
+///
+/// The card index is `4 * rank + suit`, where rank 0 corresponds to the deuce and 12 to the ace,
+/// and suits 0-3 correspond to spades, hearts, clubs and diamonds, respectively.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Card(u8);
+
+impl Card {
+    /// Creates a new card from `rank` (0-12) and `suit` (0-3).
+    #[inline]
+    pub fn new(rank: u8, suit: u8) -> Result<Self, CardError> {
+        if rank as usize >= NUMBER_OF_RANKS {
+            Err(CardError::InvalidRank(rank))
+        } else if suit >= 4 {
+            Err(CardError::InvalidSuit(suit))
+        } else {
+            Ok(Self(rank * 4 + suit))
+        }
+    }
+
+    /// Returns the rank of `self` (0 corresponds to the deuce, and 12 corresponds to the ace).
+    #[inline]
+    pub fn rank(&self) -> u8 {
+        self.0 / 4
+    }
+
+    /// Returns the suit of `self` (0-3 correspond to spades, hearts, clubs and diamonds).
+    #[inline]
+    pub fn suit(&self) -> u8 {
+        self.0 % 4
+    }
+
+    /// Returns the card index of `self` in the range \[0, 51\].
+    #[inline]
+    pub fn index(&self) -> usize {
+        self.0 as usize
+    }
+}
+
+impl TryFrom<usize> for Card {
+    type Error = CardError;
+
+    #[inline]
+    fn try_from(index: usize) -> Result<Self, Self::Error> {
+        if index < NUMBER_OF_CARDS {
+            Ok(Self(index as u8))
+        } else {
+            Err(CardError::InvalidIndex(index))
+        }
+    }
+}
+
+impl From<Card> for usize {
+    #[inline]
+    fn from(card: Card) -> Self {
+        card.index()
+    }
+}
+
+/// Formats the card as a rank character followed by a suit character (e.g., "As", "Td").
+impl fmt::Display for Card {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let rank = rank_to_string(self.rank());
+        write!(f, "{}{}", rank, suit_to_string(self.suit()))
+    }
+}
+
+impl FromStr for Card {
+    type Err = String;
+
+    fn from_str(card_str: &str) -> Result<Self, Self::Err> {
+        let mut chars = card_str.chars();
+        let rank_char = chars
+            .next()
+            .ok_or("parse failed: expected rank character, but got EOF")?;
+        let suit_char = chars
+            .next()
+            .ok_or("parse failed: expected suit character, but got EOF")?;
+        let rank_id: u8 = match rank_char.to_ascii_uppercase() {
+            '2' => Ok(0),
+            '3' => Ok(1),
+            '4' => Ok(2),
+            '5' => Ok(3),
+            '6' => Ok(4),
+            '7' => Ok(5),
+            '8' => Ok(6),
+            '9' => Ok(7),
+            'T' => Ok(8),
+            'J' => Ok(9),
+            'Q' => Ok(10),
+            'K' => Ok(11),
+            'A' => Ok(12),
+            ch => Err(format!(
+                "parse failed: expected rank character, but got '{}'",
+                ch
+            )),
+        }?;
+        let suit_id: u8 = match suit_char.to_ascii_lowercase() {
+            's' => Ok(0),
+            'h' => Ok(1),
+            'c' => Ok(2),
+            'd' => Ok(3),
+            ch => Err(format!(
+                "parse failed: expected suit character, but got '{}'",
+                ch
+            )),
+        }?;
+        if let Some(ch) = chars.next() {
+            return Err(format!("parse failed: expected EOF, but got '{}'", ch));
+        }
+        Ok(Self(rank_id * 4 + suit_id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_card_new() {
+        for rank in 0..13 {
+            for suit in 0..4 {
+                let card = Card::new(rank, suit).unwrap();
+                assert_eq!(card.rank(), rank);
+                assert_eq!(card.suit(), suit);
+                assert_eq!(card.index(), (rank * 4 + suit) as usize);
+            }
+        }
+        assert_eq!(Card::new(13, 0), Err(CardError::InvalidRank(13)));
+        assert_eq!(Card::new(255, 0), Err(CardError::InvalidRank(255)));
+        assert_eq!(Card::new(0, 4), Err(CardError::InvalidSuit(4)));
+        assert_eq!(Card::new(12, 255), Err(CardError::InvalidSuit(255)));
+    }
+
+    #[test]
+    fn test_card_encoding() {
+        // card ID: 0-3 => 2s2h2c2d, 4-7 => 3s3h3c3d, ..., 48-51 => AsAhAcAd
+        let cards = (0..NUMBER_OF_CARDS)
+            .map(|index| Card::try_from(index).unwrap().to_string())
+            .collect::<String>();
+        assert!(cards.starts_with("2s2h2c2d3s3h3c3d"));
+        assert!(cards.ends_with("AsAhAcAd"));
+        for index in 0..NUMBER_OF_CARDS {
+            let card = Card::try_from(index).unwrap();
+            assert_eq!(card.to_string().parse(), Ok(card));
+        }
+    }
+
+    #[test]
+    fn test_card_index() {
+        assert_eq!(card_index(12, 0), 48);
+        assert_eq!(card_index(0, 2), 2);
+        assert_eq!(card_index(11, 3), "Kd".parse::<Card>().unwrap().index());
+    }
+
+    #[test]
+    fn test_card_rank_and_suit() {
+        for rank in 0..13 {
+            for suit in 0..4 {
+                assert_eq!(card_rank(card_index(rank, suit)), rank);
+                assert_eq!(card_suit(card_index(rank, suit)), suit);
+            }
+        }
+        for index in 0..NUMBER_OF_CARDS {
+            let card = Card::try_from(index).unwrap();
+            assert_eq!(
+                (card_rank(index), card_suit(index)),
+                (card.rank(), card.suit())
+            );
+        }
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic]
+    fn test_card_index_invalid_rank() {
+        card_index(13, 0);
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic]
+    fn test_card_index_invalid_suit() {
+        card_index(0, 4);
+    }
+
+    #[test]
+    fn test_card_try_from() {
+        for index in 0..NUMBER_OF_CARDS {
+            let card = Card::try_from(index).unwrap();
+            assert_eq!(usize::from(card), index);
+        }
+        assert_eq!(Card::try_from(52), Err(CardError::InvalidIndex(52)));
+        assert_eq!(Card::try_from(255), Err(CardError::InvalidIndex(255)));
+        assert_eq!(
+            Card::try_from(usize::MAX),
+            Err(CardError::InvalidIndex(usize::MAX))
+        );
+    }
+
+    #[test]
+    fn test_to_string() {
+        assert_eq!(rank_to_string(0), "2");
+        assert_eq!(rank_to_string(8), "T");
+        assert_eq!(rank_to_string(12), "A");
+        assert_eq!(suit_to_string(0), "s");
+        assert_eq!(suit_to_string(3), "d");
+        for rank in 0..13 {
+            for suit in 0..4 {
+                let card = Card::new(rank, suit).unwrap();
+                let card_str = format!("{}{}", rank_to_string(rank), suit_to_string(suit));
+                assert_eq!(card.to_string(), card_str);
+                assert_eq!(card_str.parse(), Ok(card));
+            }
+        }
+    }
+
+    #[test]
+    fn test_card_parser() {
+        for index in 0..NUMBER_OF_CARDS {
+            let card = Card::try_from(index).unwrap();
+            assert_eq!(card.to_string().parse::<Card>(), Ok(card));
+        }
+        assert_eq!("As".parse::<Card>(), Ok(Card::new(12, 0).unwrap()));
+        assert_eq!("2c".parse::<Card>(), Ok(Card::new(0, 2).unwrap()));
+        assert_eq!("td".parse::<Card>(), Ok(Card::new(8, 3).unwrap()));
+        assert_eq!("KH".parse::<Card>(), Ok(Card::new(11, 1).unwrap()));
+        assert_eq!(Card::new(12, 0).unwrap().to_string(), "As");
+        assert_eq!(Card::new(8, 3).unwrap().to_string(), "Td");
+
+        assert_eq!(
+            "".parse::<Card>(),
+            Err("parse failed: expected rank character, but got EOF".into())
+        );
+        assert_eq!(
+            "A".parse::<Card>(),
+            Err("parse failed: expected suit character, but got EOF".into())
+        );
+        assert_eq!(
+            "Ax".parse::<Card>(),
+            Err("parse failed: expected suit character, but got 'x'".into())
+        );
+        assert_eq!(
+            "1s".parse::<Card>(),
+            Err("parse failed: expected rank character, but got '1'".into())
+        );
+        assert_eq!(
+            "AsK".parse::<Card>(),
+            Err("parse failed: expected EOF, but got 'K'".into())
+        );
+    }
+}
@@ -0,0 +1,144 @@
+use crate::card::*;
+use crate::hand::*;
+use assets::constants::*;
+use std::convert::TryFrom;
+
+/// A set of cards without the evaluation data of `Hand`, where bit `i` of the inner mask
+/// represents the card of index `i`. Unlike `Hand`, any number of cards can be stored.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct CardSet(pub u64);
+
+impl CardSet {
+    /// Creates a new empty set.
+    #[inline]
+    pub fn new() -> Self {
+        Self(0)
+    }
+
+    /// Checks whether the set is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// Returns the number of cards in `self`.
+    #[inline]
+    pub fn count(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    /// Returns whether `card` is included in `self`.
+    #[inline]
+    pub fn contains(&self, card: Card) -> bool {
+        (self.0 >> card.index()) & 1 != 0
+    }
+
+    /// Adds `card` to `self`. Does nothing when `card` is already included.
+    #[inline]
+    pub fn insert(&mut self, card: Card) {
+        self.0 |= 1 << card.index();
+    }
+
+    /// Removes `card` from `self`. Does nothing when `card` is not included.
+    #[inline]
+    pub fn remove(&mut self, card: Card) {
+        self.0 &= !(1 << card.index());
+    }
+
+    /// Returns the set of cards included in `self` or `other`.
+    #[inline]
+    pub fn union(&self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+
+    /// Returns the set of cards included in both `self` and `other`.
+    #[inline]
+    pub fn intersection(&self, other: Self) -> Self {
+        Self(self.0 & other.0)
+    }
+
+    /// Returns the set of cards included in `self` but not in `other`.
+    #[inline]
+    pub fn difference(&self, other: Self) -> Self {
+        Self(self.0 & !other.0)
+    }
+
+    /// Returns the bit mask of `self` in the layout of `Hand::get_mask()`.
+    #[inline]
+    pub(crate) fn get_hand_mask(&self) -> u64 {
+        (0..NUMBER_OF_CARDS)
+            .filter(|i| (self.0 >> i) & 1 != 0)
+            .fold(0, |mask, i| mask | CARDS[i].1)
+    }
+}
+
+impl From<Hand> for CardSet {
+    #[inline]
+    fn from(hand: Hand) -> Self {
+        let mut set = Self::new();
+        for card in hand.cards() {
+            set.insert(card);
+        }
+        set
+    }
+}
+
+/// Fails when `card_set` has more than 7 cards or any bit above 51 set.
+impl TryFrom<CardSet> for Hand {
+    type Error = HandError;
+
+    #[inline]
+    fn try_from(card_set: CardSet) -> Result<Self, Self::Error> {
+        Hand::from_u64_mask(card_set.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::combinations::*;
+
+    #[test]
+    fn test_card_set() {
+        let card = |card_str: &str| card_str.parse::<Card>().unwrap();
+        let mut set = CardSet::new();
+        assert!(set.is_empty());
+        set.insert(card("As"));
+        set.insert(card("Kd"));
+        set.insert(card("As"));
+        assert_eq!(set.count(), 2);
+        assert!(set.contains(card("As")) && !set.contains(card("Ah")));
+        set.remove(card("As"));
+        set.remove(card("Ah"));
+        assert_eq!(set, CardSet(1 << 47));
+
+        let a = CardSet::from("AsKsQs".parse::<Hand>().unwrap());
+        let b = CardSet::from("QsJsTs".parse::<Hand>().unwrap());
+        assert_eq!(a.union(b).count(), 5);
+        assert_eq!(
+            a.intersection(b),
+            CardSet::from("Qs".parse::<Hand>().unwrap())
+        );
+        assert_eq!(
+            a.difference(b),
+            CardSet::from("AsKs".parse::<Hand>().unwrap())
+        );
+
+        let full = CardSet((1 << 52) - 1);
+        assert_eq!(full.count(), 52);
+        assert_eq!(Hand::try_from(full), Err(HandError::TooManyCards(52)));
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for n in 0..=5 {
+            for hand in AllHandsIterator::new(n).step_by(7) {
+                assert_eq!(Hand::try_from(CardSet::from(hand)), Ok(hand));
+                assert_eq!(CardSet::from(hand).get_hand_mask(), hand.get_mask());
+            }
+        }
+        for hand in AllHandsIterator::new(7).step_by(100003) {
+            assert_eq!(Hand::try_from(CardSet::from(hand)), Ok(hand));
+        }
+    }
+}
@@ -0,0 +1,127 @@
+use crate::hand::*;
+
+/// Rows of a Chinese Poker (Open-Face Chinese) hand.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum ChinesePokerPosition {
+    /// the 3-card front (top) row
+    Front,
+    /// the 5-card middle row
+    Middle,
+    /// the 5-card back (bottom) row
+    Back,
+}
+
+impl Hand {
+    /// Returns the royalty points of `self` placed in `position`, following the standard table
+    /// of Open-Face Chinese Poker:
+    ///
+    /// * Back: straight 2, flush 4, full house 6, four of a kind 10, straight flush 15,
+    ///   royal flush 25
+    /// * Middle: three of a kind 2, straight 4, flush 8, full house 12, four of a kind 20,
+    ///   straight flush 30, royal flush 50
+    /// * Front: pair of sixes 1 up to pair of aces 9, three deuces 10 up to three aces 22
+    ///
+    /// `self` must consist of 3 cards for the front row and 5 cards for the other rows.
+    pub fn evaluate_chinese_poker_royalties(&self, position: ChinesePokerPosition) -> i32 {
+        if position == ChinesePokerPosition::Front {
+            assert_eq!(self.len(), 3);
+            return match (0..13).rev().find(|rank| self.count_rank(*rank) >= 2) {
+                Some(rank) if self.count_rank(rank) == 3 => 10 + rank as i32,
+                Some(rank) if rank >= 4 => rank as i32 - 3,
+                _ => 0,
+            };
+        }
+
+        assert_eq!(self.len(), 5);
+        let rank = self.evaluate();
+        let is_royal = rank == *HandCategory::StraightFlush.rank_range().end();
+        let back = match get_hand_category(rank) {
+            HandCategory::StraightFlush if is_royal => 25,
+            HandCategory::StraightFlush => 15,
+            HandCategory::FourOfAKind => 10,
+            HandCategory::FullHouse => 6,
+            HandCategory::Flush => 4,
+            HandCategory::Straight => 2,
+            _ => 0,
+        };
+        match position {
+            ChinesePokerPosition::Back => back,
+            _ if get_hand_category(rank) == HandCategory::ThreeOfAKind => 2,
+            _ => 2 * back,
+        }
+    }
+
+    /// Returns the strength of 3-card `self` as the front row of Chinese Poker, where straights and
+    /// flushes do not count: three of a kind beats any pair, and pairs (compared by the pair rank,
+    /// then the kicker) beat any high card hand. Higher values are stronger, and
+    /// `get_hand_category()` of the return value gives `ThreeOfAKind`, `OnePair` or `HighCard`.
+    #[inline]
+    pub fn evaluate_chinese_front_3(&self) -> u16 {
+        assert_eq!(self.len(), 3);
+        self.evaluate_n(3)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::combinations::*;
+    use std::collections::HashSet;
+
+    fn royalties(hand_str: &str, position: ChinesePokerPosition) -> i32 {
+        hand_str
+            .parse::<Hand>()
+            .unwrap()
+            .evaluate_chinese_poker_royalties(position)
+    }
+
+    #[test]
+    fn test_royalties() {
+        use ChinesePokerPosition::*;
+
+        assert_eq!(royalties("AsKsQsJsTs", Back), 25);
+        assert_eq!(royalties("AsKsQsJsTs", Middle), 50);
+        assert_eq!(royalties("9s8s7s6s5s", Back), 15);
+        assert_eq!(royalties("As2s3s4s5s", Middle), 30);
+        assert_eq!(royalties("AsAhAcAd2s", Back), 10);
+        assert_eq!(royalties("2s2h2c3d3s", Middle), 12);
+        assert_eq!(royalties("As9s7s4s2s", Back), 4);
+        assert_eq!(royalties("As2h3c4d5s", Back), 2);
+        assert_eq!(royalties("As2h3c4d5s", Middle), 4);
+        assert_eq!(royalties("AsAhAcKdQs", Back), 0);
+        assert_eq!(royalties("AsAhAcKdQs", Middle), 2);
+        assert_eq!(royalties("AsAhKcKdQs", Middle), 0);
+
+        assert_eq!(royalties("AsAhAc", Front), 22);
+        assert_eq!(royalties("2s2h2c", Front), 10);
+        assert_eq!(royalties("AsAhKc", Front), 9);
+        assert_eq!(royalties("6s6h2c", Front), 1);
+        assert_eq!(royalties("5s5hAc", Front), 0);
+        assert_eq!(royalties("AsKhQc", Front), 0);
+    }
+
+    #[test]
+    fn test_evaluate_chinese_front_3() {
+        let front = |hand_str: &str| hand_str.parse::<Hand>().unwrap().evaluate_chinese_front_3();
+        assert!(front("AsAhAc") > front("AsAhKc"));
+        assert!(front("AsAhKc") > front("AsKhQc"));
+        assert!(front("2s2h2c") > front("AsAhKc"));
+        assert!(front("AsAhKc") > front("AsAhQc"));
+        assert!(front("3s3h2c") > front("2s2hAc"));
+        assert!(front("AsKhJc") > front("AsQhJc"));
+        // suits do not matter
+        assert_eq!(front("AsKsQs"), front("AsKhQc"));
+        assert_eq!(get_hand_category(front("4s3s2s")), HandCategory::HighCard);
+
+        let mut counter = [0; HandCategory::StraightFlush as usize + 1];
+        let mut rankset = HashSet::new();
+        for hand in AllHandsIterator::new(3) {
+            let rank = hand.evaluate_chinese_front_3();
+            counter[get_hand_category(rank) as usize] += 1;
+            rankset.insert(rank);
+        }
+        assert_eq!(counter, [18304, 3744, 0, 52, 0, 0, 0, 0, 0]);
+        assert_eq!(counter.iter().sum::<usize>(), 22100);
+        assert_eq!(rankset.len(), 286 + 156 + 13);
+    }
+}
@@ -0,0 +1,379 @@
+use crate::card::*;
+use crate::card_set::*;
+use crate::hand::*;
+use assets::constants::*;
+use std::convert::TryFrom;
+
+/// Returns the binomial coefficient C(`n`, `k`), i.e., the number of ways to choose `k` cards out
+/// of `n` cards. Returns 0 when `k > n`.
+/// This function panics when the result does not fit in `u64`.
+pub fn count_combinations(n: usize, k: usize) -> u64 {
+    if k > n {
+        return 0;
+    }
+    let k = k.min(n - k);
+    // each intermediate value C(n, i + 1) does not exceed the result since `k <= n / 2`
+    let mut result = 1u128;
+    for i in 0..k {
+        result = result * (n - i) as u128 / (i + 1) as u128;
+        assert!(result <= u64::MAX as u128);
+    }
+    result as u64
+}
+
+/// Iterator over all `n`-card subsets of a hand, created by `HandCombinations::new()`.
+#[derive(Clone, Debug)]
+pub struct HandCombinations {
+    cards: CardArray,
+    subset: u32,
+    end: u32,
+}
+
+impl HandCombinations {
+    /// Creates an iterator that yields every hand consisting of exactly `n` cards of `hand`, in
+    /// colexicographic order of the card positions in `hand.cards()`.
+    /// Nothing is yielded when `n > hand.len()`.
+    #[inline]
+    pub fn new(hand: Hand, n: usize) -> Self {
+        let end = 1 << hand.len();
+        Self {
+            cards: hand.to_card_array(),
+            subset: if n <= hand.len() { (1 << n) - 1 } else { end },
+            end,
+        }
+    }
+}
+
+impl Iterator for HandCombinations {
+    type Item = Hand;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.subset >= self.end {
+            return None;
+        }
+        let subset = self.subset;
+        let hand = self
+            .cards
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| (subset >> i) & 1 != 0)
+            .fold(Hand::new(), |hand, (_, card)| hand.add_card(*card));
+        // Gosper's hack: the next larger integer with the same number of bits set
+        self.subset = if subset == 0 {
+            self.end
+        } else {
+            let u = subset & subset.wrapping_neg();
+            let v = subset + u;
+            v + (((v ^ subset) / u) >> 2)
+        };
+        Some(hand)
+    }
+}
+
+/// Iterator over all `n`-card hands from the full deck, created by `AllHandsIterator::new()`.
+#[derive(Clone, Debug)]
+pub struct AllHandsIterator {
+    indices: [usize; 7],
+    n: usize,
+    remaining: usize,
+}
+
+impl AllHandsIterator {
+    /// Creates an iterator that yields all C(52, `n`) hands of `n` cards (0-7), in lexicographic
+    /// order of card indices.
+    #[inline]
+    pub fn new(n: usize) -> Self {
+        assert!(n <= 7);
+        let mut indices = [0; 7];
+        for i in 0..n {
+            indices[i] = i;
+        }
+        Self {
+            indices,
+            n,
+            remaining: count_combinations(NUMBER_OF_CARDS, n) as usize,
+        }
+    }
+}
+
+impl Iterator for AllHandsIterator {
+    type Item = Hand;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        let n = self.n;
+        let hand = self.indices[..n].iter().fold(Hand::new(), |hand, i| {
+            hand.add_card(Card::try_from(*i).unwrap())
+        });
+        if let Some(i) = (0..n)
+            .rev()
+            .find(|i| self.indices[*i] < NUMBER_OF_CARDS - n + i)
+        {
+            self.indices[i] += 1;
+            for j in (i + 1)..n {
+                self.indices[j] = self.indices[j - 1] + 1;
+            }
+        }
+        Some(hand)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl ExactSizeIterator for AllHandsIterator {}
+
+/// Returns a lazy iterator over all completed 5-card boards of `board` (0-5 cards), dealing
+/// `5 - board.len()` cards from the deck without the cards of `board` and `dead`.
+pub fn enumerate_runouts(board: Hand, dead: CardSet) -> impl Iterator<Item = Hand> {
+    enumerate_runout_deltas(board, dead).map(move |delta| board + delta)
+}
+
+/// Returns a lazy iterator over the same runouts as `enumerate_runouts()`, but each yielded hand
+/// consists of only the `5 - board.len()` dealt cards; e.g., `hole + board + delta` is the
+/// player's final hand.
+pub fn enumerate_runout_deltas(board: Hand, dead: CardSet) -> impl Iterator<Item = Hand> {
+    RunoutDeltas::new(board, dead)
+}
+
+/// Iterator over the card sets dealt to complete a board, in lexicographic order of card indices.
+#[derive(Clone, Debug)]
+struct RunoutDeltas {
+    alive_cards: Vec<Card>,
+    indices: [usize; 5],
+    n: usize,
+    is_done: bool,
+}
+
+impl RunoutDeltas {
+    fn new(board: Hand, dead: CardSet) -> Self {
+        assert!(board.len() <= 5);
+        let mask = board.get_mask() | dead.get_hand_mask();
+        let alive_cards = (0..NUMBER_OF_CARDS)
+            .filter(|i| (CARDS[*i].1 & mask) == 0)
+            .map(|i| Card::try_from(i).unwrap())
+            .collect::<Vec<_>>();
+        let n = 5 - board.len();
+        Self {
+            is_done: n > alive_cards.len(),
+            alive_cards,
+            indices: [0, 1, 2, 3, 4],
+            n,
+        }
+    }
+}
+
+impl Iterator for RunoutDeltas {
+    type Item = Hand;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.is_done {
+            return None;
+        }
+        let (n, len) = (self.n, self.alive_cards.len());
+        let delta = self.indices[..n]
+            .iter()
+            .fold(Hand::new(), |hand, i| hand.add_card(self.alive_cards[*i]));
+        match (0..n).rev().find(|i| self.indices[*i] < len - n + i) {
+            Some(i) => {
+                self.indices[i] += 1;
+                for j in (i + 1)..n {
+                    self.indices[j] = self.indices[j - 1] + 1;
+                }
+            }
+            None => self.is_done = true,
+        }
+        Some(delta)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_count_combinations() {
+        assert_eq!(count_combinations(52, 5), 2_598_960);
+        assert_eq!(count_combinations(52, 7), 133_784_560);
+        assert_eq!(count_combinations(52, 2), 1326);
+        assert_eq!(count_combinations(5, 7), 0);
+        assert_eq!(count_combinations(0, 0), 1);
+        assert_eq!(count_combinations(0, 1), 0);
+        assert_eq!(count_combinations(7, 0), 1);
+        assert_eq!(count_combinations(7, 7), 1);
+        assert_eq!(count_combinations(67, 33), 14_226_520_737_620_288_370);
+        for n in 1..30 {
+            for k in 1..n {
+                let sum = count_combinations(n - 1, k - 1) + count_combinations(n - 1, k);
+                assert_eq!(count_combinations(n, k), sum);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_count_combinations_overflow() {
+        count_combinations(68, 34);
+    }
+
+    #[test]
+    fn test_hand_combinations() {
+        let hand = "AsKhQcJdTs9h2c".parse::<Hand>().unwrap();
+        assert_eq!(HandCombinations::new(hand, 5).count(), 21);
+        assert_eq!(HandCombinations::new(hand, 7).count(), 1);
+        assert_eq!(HandCombinations::new(hand, 8).count(), 0);
+        assert_eq!(
+            HandCombinations::new(hand, 0).collect::<Vec<_>>(),
+            vec![Hand::new()]
+        );
+        assert_eq!(
+            HandCombinations::new(Hand::new(), 0).collect::<Vec<_>>(),
+            vec![Hand::new()]
+        );
+
+        for n in 0..=7 {
+            let subsets = HandCombinations::new(hand, n).collect::<Vec<_>>();
+            let distinct = subsets.iter().collect::<HashSet<_>>();
+            assert_eq!(distinct.len(), subsets.len());
+            assert!(subsets
+                .iter()
+                .all(|h| h.len() == n && h.shared_cards(&hand) == *h));
+        }
+
+        // the same 5-card hands as the nested loops
+        let cards = hand.cards().collect::<Vec<_>>();
+        let mut expected = Vec::new();
+        for i in 0..3 {
+            for j in (i + 1)..4 {
+                for k in (j + 1)..5 {
+                    for m in (k + 1)..6 {
+                        for n in (m + 1)..7 {
+                            let five = [cards[i], cards[j], cards[k], cards[m], cards[n]];
+                            expected.push(Hand::from_slice(&five).evaluate());
+                        }
+                    }
+                }
+            }
+        }
+        let mut ranks = HandCombinations::new(hand, 5)
+            .map(|h| h.evaluate())
+            .collect::<Vec<_>>();
+        ranks.sort_unstable();
+        expected.sort_unstable();
+        assert_eq!(ranks, expected);
+        assert_eq!(ranks.iter().max(), Some(&hand.evaluate()));
+    }
+
+    #[test]
+    fn test_all_hands_iterator() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<AllHandsIterator>();
+
+        assert_eq!(AllHandsIterator::new(7).len(), 133784560);
+        assert_eq!(
+            AllHandsIterator::new(0).collect::<Vec<_>>(),
+            vec![Hand::new()]
+        );
+        assert_eq!(AllHandsIterator::new(1).count(), 52);
+        assert_eq!(AllHandsIterator::new(2).skip(1000).len(), 1326 - 1000);
+        assert_eq!(AllHandsIterator::new(2).skip(1000).count(), 1326 - 1000);
+
+        // lexicographic order
+        let card = |i: usize| Card::try_from(i).unwrap();
+        let mut iter = AllHandsIterator::new(3);
+        for i in 0..(NUMBER_OF_CARDS - 2) {
+            for j in (i + 1)..(NUMBER_OF_CARDS - 1) {
+                for k in (j + 1)..NUMBER_OF_CARDS {
+                    assert_eq!(
+                        iter.next(),
+                        Some(Hand::from_slice(&[card(i), card(j), card(k)]))
+                    );
+                }
+            }
+        }
+        assert_eq!(iter.len(), 0);
+        assert_eq!(iter.next(), None);
+
+        let mut counter = [0; HandCategory::StraightFlush as usize + 1];
+        for hand in AllHandsIterator::new(5) {
+            counter[get_hand_category(hand.evaluate()) as usize] += 1;
+        }
+        assert_eq!(
+            counter,
+            [1302540, 1098240, 123552, 54912, 10200, 5108, 3744, 624, 40]
+        );
+    }
+
+    #[test]
+    fn test_enumerate_runouts() {
+        let hand = |hand_str: &str| hand_str.parse::<Hand>().unwrap();
+        let board = hand("AsKd7h");
+        let dead = CardSet::from(hand("QcJc"));
+        assert_eq!(enumerate_runouts(board, CardSet::new()).count(), 1176);
+        assert_eq!(enumerate_runouts(board, dead).count(), 1081);
+        assert_eq!(enumerate_runouts(hand("AsKd7h2c"), dead).count(), 46);
+        assert_eq!(
+            enumerate_runouts(hand("AsKd7h2c3c"), dead).collect::<Vec<_>>(),
+            vec![hand("AsKd7h2c3c")]
+        );
+        assert_eq!(
+            enumerate_runouts(Hand::new(), CardSet::new()).count(),
+            2598960
+        );
+
+        let runouts = enumerate_runouts(board, dead).collect::<Vec<_>>();
+        assert_eq!(runouts.iter().collect::<HashSet<_>>().len(), runouts.len());
+        for runout in &runouts {
+            assert_eq!(runout.len(), 5);
+            assert_eq!(runout.shared_cards(&board), board);
+            assert!(CardSet::from(*runout).intersection(dead).is_empty());
+        }
+    }
+
+    #[test]
+    fn test_enumerate_runout_deltas() {
+        let hand = |hand_str: &str| hand_str.parse::<Hand>().unwrap();
+        let hole = hand("QcJc");
+        for board in &[hand("AsKd7h"), hand("AsKd7h2c"), hand("AsKd7h2c3c")] {
+            let board = *board;
+            let deltas = enumerate_runout_deltas(board, CardSet::from(hole)).collect::<Vec<_>>();
+            assert!(deltas.iter().all(|delta| delta.len() == 5 - board.len()));
+            let hands = deltas
+                .iter()
+                .map(|delta| hole + board + *delta)
+                .collect::<Vec<_>>();
+            assert!(hands.iter().all(|hand| hand.len() == 7 && hand.is_valid()));
+            assert_eq!(
+                hands.len(),
+                enumerate_runouts(board, CardSet::from(hole)).count()
+            );
+            assert!(enumerate_runouts(board, CardSet::from(hole))
+                .zip(&deltas)
+                .all(|(runout, delta)| runout == board + *delta));
+        }
+        assert_eq!(
+            enumerate_runout_deltas(Hand::new(), CardSet::new()).count(),
+            2598960
+        );
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_all_hands_iterator_par_bridge() {
+        use rayon::iter::{ParallelBridge, ParallelIterator};
+        let flushes = AllHandsIterator::new(5)
+            .par_bridge()
+            .filter(|hand| get_hand_category(hand.evaluate()) == HandCategory::Flush)
+            .count();
+        assert_eq!(flushes, 5108);
+    }
+}
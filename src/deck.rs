@@ -0,0 +1,150 @@
+use crate::card::*;
+use crate::hand::*;
+use assets::constants::*;
+use std::convert::TryFrom;
+
+#[cfg(feature = "rand")]
+use rand::{seq::SliceRandom, Rng};
+
+/// A deck of remaining cards. Cards are dealt from the top of the deck.
+///
+/// The remaining cards are tracked as a bit mask in the same layout as `Hand::get_mask()`, so
+/// that `contains()` and `get_mask()` take constant time. The order of the cards is only kept for
+/// dealing and shuffling.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Deck {
+    mask: u64,
+    order: Vec<Card>,
+}
+
+impl Deck {
+    /// Creates a full deck of 52 cards in ascending order of card index.
+    pub fn new() -> Self {
+        Self {
+            mask: CARDS.iter().fold(0, |mask, card| mask | card.1),
+            order: (0..NUMBER_OF_CARDS)
+                .map(|i| Card::try_from(i).unwrap())
+                .collect(),
+        }
+    }
+
+    /// Checks whether the deck is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.mask == 0
+    }
+
+    /// Returns current number of cards in `self`.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.mask.count_ones() as usize
+    }
+
+    /// Returns the bit mask of the remaining cards in the same layout as `Hand::get_mask()`.
+    #[inline]
+    pub fn get_mask(&self) -> u64 {
+        self.mask
+    }
+
+    /// Returns whether `card` remains in `self`.
+    #[inline]
+    pub fn contains(&self, card: Card) -> bool {
+        self.mask & CARDS[card.index()].1 != 0
+    }
+
+    /// Deals `n` cards from the top of the deck and returns them as a new hand.
+    /// This function panics when `n` is greater than 7 (`Hand` holds at most 7 cards) or than the
+    /// number of remaining cards.
+    pub fn deal(&mut self, n: usize) -> Hand {
+        assert!(n <= 7);
+        assert!(n <= self.len());
+        let mut hand = Hand::new();
+        for _ in 0..n {
+            hand = hand.add_card(self.order.pop().unwrap());
+        }
+        self.mask &= !hand.get_mask();
+        hand
+    }
+
+    /// Removes the cards in `hand` from `self` (e.g., known hole cards and board cards).
+    pub fn remove_hand(&mut self, hand: &Hand) {
+        self.mask &= !hand.get_mask();
+        self.order.retain(|card| !hand.contains(*card));
+    }
+
+    /// Shuffles the remaining cards.
+    #[cfg(feature = "rand")]
+    pub fn shuffle<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        self.order.shuffle(rng);
+    }
+}
+
+impl Default for Deck {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deal() {
+        let mut deck = Deck::new();
+        assert_eq!(deck.len(), 52);
+        assert_eq!(deck.get_mask(), 0x1fff_1fff_1fff_1fff);
+
+        let hand = deck.deal(2);
+        assert_eq!(hand, "AcAd".parse::<Hand>().unwrap());
+        assert_eq!(deck.len(), 50);
+        assert_eq!(deck.get_mask() & hand.get_mask(), 0);
+
+        let board = "AsKsQs".parse::<Hand>().unwrap();
+        deck.remove_hand(&board);
+        assert_eq!(deck.len(), 47);
+        assert!(!deck.contains("As".parse().unwrap()));
+        assert!(deck.contains("Ah".parse().unwrap()));
+        assert!(!deck.contains("Ac".parse().unwrap()));
+
+        let hand = deck.deal(7);
+        assert_eq!(hand.len(), 7);
+        assert!(hand.is_valid());
+        assert_eq!(deck.len(), 40);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_deal_underflow() {
+        let mut deck = Deck::new();
+        for _ in 0..7 {
+            deck.deal(7);
+        }
+        deck.deal(7);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_deal_too_many() {
+        Deck::new().deal(8);
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn test_shuffle() {
+        use rand::{rngs::StdRng, SeedableRng};
+
+        let mut rng = StdRng::seed_from_u64(0);
+        for _ in 0..10000 {
+            let mut deck = Deck::new();
+            deck.shuffle(&mut rng);
+            let hand1 = deck.deal(2);
+            let hand2 = deck.deal(2);
+            let board = deck.deal(5);
+            let mask = hand1.get_mask() | hand2.get_mask() | board.get_mask();
+            assert_eq!(mask.count_ones(), 9);
+            assert_eq!(deck.get_mask() & mask, 0);
+            assert_eq!(deck.len(), 43);
+        }
+    }
+}
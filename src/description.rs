@@ -0,0 +1,436 @@
+use crate::card::*;
+use crate::combinations::*;
+use crate::hand::*;
+use assets::constants::*;
+
+const RANK_NAMES: [&str; NUMBER_OF_RANKS] = [
+    "Two", "Three", "Four", "Five", "Six", "Seven", "Eight", "Nine", "Ten", "Jack", "Queen",
+    "King", "Ace",
+];
+
+const RANK_NAMES_PLURAL: [&str; NUMBER_OF_RANKS] = [
+    "Twos", "Threes", "Fours", "Fives", "Sixes", "Sevens", "Eights", "Nines", "Tens", "Jacks",
+    "Queens", "Kings", "Aces",
+];
+
+/// generic names of hands by category, as pairs of the lowest possible primary rank (e.g., the
+/// pair rank of one pairs) and the names from that rank up
+const HAND_NAMES: [(usize, &[&str]); 9] = [
+    // high cards
+    (
+        5,
+        &[
+            "Seven High",
+            "Eight High",
+            "Nine High",
+            "Ten High",
+            "Jack High",
+            "Queen High",
+            "King High",
+            "Ace High",
+        ],
+    ),
+    // one pairs
+    (
+        0,
+        &[
+            "Pair of Twos",
+            "Pair of Threes",
+            "Pair of Fours",
+            "Pair of Fives",
+            "Pair of Sixes",
+            "Pair of Sevens",
+            "Pair of Eights",
+            "Pair of Nines",
+            "Pair of Tens",
+            "Pair of Jacks",
+            "Pair of Queens",
+            "Pair of Kings",
+            "Pair of Aces",
+        ],
+    ),
+    // two pairs
+    (
+        1,
+        &[
+            "Threes Up",
+            "Fours Up",
+            "Fives Up",
+            "Sixes Up",
+            "Sevens Up",
+            "Eights Up",
+            "Nines Up",
+            "Tens Up",
+            "Jacks Up",
+            "Queens Up",
+            "Kings Up",
+            "Aces Up",
+        ],
+    ),
+    // three of a kinds
+    (
+        0,
+        &[
+            "Three Twos",
+            "Three Threes",
+            "Three Fours",
+            "Three Fives",
+            "Three Sixes",
+            "Three Sevens",
+            "Three Eights",
+            "Three Nines",
+            "Three Tens",
+            "Three Jacks",
+            "Three Queens",
+            "Three Kings",
+            "Three Aces",
+        ],
+    ),
+    // straights (the five-high and ace-high ones have special names)
+    (
+        4,
+        &[
+            "Six-high Straight",
+            "Seven-high Straight",
+            "Eight-high Straight",
+            "Nine-high Straight",
+            "Ten-high Straight",
+            "Jack-high Straight",
+            "Queen-high Straight",
+            "King-high Straight",
+        ],
+    ),
+    // flushes
+    (
+        5,
+        &[
+            "Seven-high Flush",
+            "Eight-high Flush",
+            "Nine-high Flush",
+            "Ten-high Flush",
+            "Jack-high Flush",
+            "Queen-high Flush",
+            "King-high Flush",
+            "Ace-high Flush",
+        ],
+    ),
+    // full houses
+    (
+        0,
+        &[
+            "Twos Full",
+            "Threes Full",
+            "Fours Full",
+            "Fives Full",
+            "Sixes Full",
+            "Sevens Full",
+            "Eights Full",
+            "Nines Full",
+            "Tens Full",
+            "Jacks Full",
+            "Queens Full",
+            "Kings Full",
+            "Aces Full",
+        ],
+    ),
+    // four of a kinds
+    (
+        0,
+        &[
+            "Four Twos",
+            "Four Threes",
+            "Four Fours",
+            "Four Fives",
+            "Four Sixes",
+            "Four Sevens",
+            "Four Eights",
+            "Four Nines",
+            "Four Tens",
+            "Four Jacks",
+            "Four Queens",
+            "Four Kings",
+            "Four Aces",
+        ],
+    ),
+    // straight flushes (the five-high and ace-high ones have special names)
+    (
+        4,
+        &[
+            "Six-high Straight Flush",
+            "Seven-high Straight Flush",
+            "Eight-high Straight Flush",
+            "Nine-high Straight Flush",
+            "Ten-high Straight Flush",
+            "Jack-high Straight Flush",
+            "Queen-high Straight Flush",
+            "King-high Straight Flush",
+        ],
+    ),
+];
+
+/// cumulative numbers of 5-card rank sets without straights by top rank, i.e., a high card or
+/// flush index is less than `NO_STRAIGHT_CUMULATIVE_COUNTS[r]` when its top rank is at most `r`
+const NO_STRAIGHT_CUMULATIVE_COUNTS: [u16; NUMBER_OF_RANKS] =
+    [0, 0, 0, 0, 0, 4, 18, 52, 121, 246, 455, 784, 1277];
+
+/// Returns a short name of the hand rank computed by `Hand::evaluate()`, e.g., "Pair of Aces".
+/// The best and worst straights and straight flushes have special names: "Royal Flush",
+/// "Steel Wheel" (A-2-3-4-5 straight flush), "Broadway" (A-K-Q-J-T straight) and "Wheel"
+/// (A-2-3-4-5 straight).
+pub fn poker_hand_name(hand_rank: u16) -> &'static str {
+    let category = get_hand_category(hand_rank);
+    let index = (hand_rank & 0x0fff) as usize;
+    assert!(index < CATEGORY_SIZES[category as usize] as usize);
+    let primary = match category {
+        HandCategory::StraightFlush if index == 9 => return "Royal Flush",
+        HandCategory::StraightFlush if index == 0 => return "Steel Wheel",
+        HandCategory::Straight if index == 9 => return "Broadway",
+        HandCategory::Straight if index == 0 => return "Wheel",
+        HandCategory::HighCard | HandCategory::Flush => NO_STRAIGHT_CUMULATIVE_COUNTS
+            .iter()
+            .position(|count| index < *count as usize)
+            .unwrap(),
+        HandCategory::OnePair => index / 220,
+        HandCategory::TwoPair => decode_ranks(index / 11, 2, &[])[0],
+        HandCategory::ThreeOfAKind => index / 66,
+        HandCategory::Straight | HandCategory::StraightFlush => index + 3,
+        HandCategory::FullHouse | HandCategory::FourOfAKind => index / 12,
+    };
+    let (lowest, names) = HAND_NAMES[category as usize];
+    names[primary - lowest]
+}
+
+/// Returns a textual description of the hand rank computed by `Hand::evaluate()`,
+/// e.g., "One Pair, Aces with K, Q, J kickers".
+pub fn get_hand_description(hand_rank: u16) -> String {
+    let category = get_hand_category(hand_rank);
+    let index = (hand_rank & 0x0fff) as usize;
+    assert!(index < CATEGORY_SIZES[category as usize] as usize);
+    match category {
+        HandCategory::HighCard => {
+            let ranks = decode_no_straight(index);
+            format!(
+                "High Card, {} high with {} kickers",
+                RANK_NAMES[ranks[0]],
+                join_ranks(&ranks[1..])
+            )
+        }
+        HandCategory::OnePair => {
+            let pair = index / 220;
+            let kickers = decode_ranks(index % 220, 3, &[pair]);
+            format!(
+                "One Pair, {} with {} kickers",
+                RANK_NAMES_PLURAL[pair],
+                join_ranks(&kickers)
+            )
+        }
+        HandCategory::TwoPair => {
+            let pairs = decode_ranks(index / 11, 2, &[]);
+            let kicker = decode_ranks(index % 11, 1, &pairs);
+            format!(
+                "Two Pair, {} and {} with {} kicker",
+                RANK_NAMES_PLURAL[pairs[0]],
+                RANK_NAMES_PLURAL[pairs[1]],
+                join_ranks(&kicker)
+            )
+        }
+        HandCategory::ThreeOfAKind => {
+            let trips = index / 66;
+            let kickers = decode_ranks(index % 66, 2, &[trips]);
+            format!(
+                "Three of a Kind, {} with {} kickers",
+                RANK_NAMES_PLURAL[trips],
+                join_ranks(&kickers)
+            )
+        }
+        HandCategory::Straight => format!("Straight, {} high", RANK_NAMES[index + 3]),
+        HandCategory::Flush => {
+            let ranks = decode_no_straight(index);
+            format!(
+                "Flush, {} high with {} kickers",
+                RANK_NAMES[ranks[0]],
+                join_ranks(&ranks[1..])
+            )
+        }
+        HandCategory::FullHouse => {
+            let trips = index / 12;
+            let pair = decode_ranks(index % 12, 1, &[trips]);
+            format!(
+                "Full House, {} full of {}",
+                RANK_NAMES_PLURAL[trips], RANK_NAMES_PLURAL[pair[0]]
+            )
+        }
+        HandCategory::FourOfAKind => {
+            let quads = index / 12;
+            let kicker = decode_ranks(index % 12, 1, &[quads]);
+            format!(
+                "Four of a Kind, {} with {} kicker",
+                RANK_NAMES_PLURAL[quads],
+                join_ranks(&kicker)
+            )
+        }
+        HandCategory::StraightFlush => format!("Straight Flush, {} high", RANK_NAMES[index + 3]),
+    }
+}
+
+fn join_ranks(ranks: &[usize]) -> String {
+    ranks
+        .iter()
+        .map(|rank| rank_to_string(*rank as u8))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Decodes `index` in the combinatorial number system into `k` ranks in descending order.
+/// Ranks in `excluded` are skipped, i.e., `index` enumerates combinations of the other ranks.
+fn decode_ranks(index: usize, k: usize, excluded: &[usize]) -> Vec<usize> {
+    let available = (0..NUMBER_OF_RANKS)
+        .filter(|rank| !excluded.contains(rank))
+        .collect::<Vec<_>>();
+    let mut index = index;
+    let mut result = Vec::new();
+    for i in (1..=k).rev() {
+        let mut c = i - 1;
+        while count_combinations(c + 1, i) as usize <= index {
+            c += 1;
+        }
+        index -= count_combinations(c, i) as usize;
+        result.push(available[c]);
+    }
+    result
+}
+
+/// Decodes `index` into five distinct ranks in descending order, where straights are skipped.
+fn decode_no_straight(index: usize) -> Vec<usize> {
+    (0..)
+        .map(|i| decode_ranks(i, 5, &[]))
+        .filter(|ranks| !is_straight(ranks))
+        .nth(index)
+        .unwrap()
+}
+
+fn is_straight(ranks: &[usize]) -> bool {
+    ranks[0] - ranks[4] == 4 || ranks == [12, 3, 2, 1, 0]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    fn describe(hand_str: &str) -> String {
+        get_hand_description(hand_str.parse::<Hand>().unwrap().evaluate())
+    }
+
+    #[test]
+    fn test_hand_description() {
+        assert_eq!(describe("AsKsQsJsTs7d5s"), "Straight Flush, Ace high");
+        assert_eq!(describe("AdQsJc5d4d3d2d"), "Straight Flush, Five high");
+        assert_eq!(
+            describe("AsAcAhAdKsQcTh"),
+            "Four of a Kind, Aces with K kicker"
+        );
+        assert_eq!(
+            describe("3d3h3s2c2d2h2s"),
+            "Four of a Kind, Twos with 3 kicker"
+        );
+        assert_eq!(describe("AsAdAhKcKdKh2d"), "Full House, Aces full of Kings");
+        assert_eq!(describe("4h4c3s3c2d2c2h"), "Full House, Twos full of Fours");
+        assert_eq!(
+            describe("AhKhQhJh9h9c9s"),
+            "Flush, Ace high with K, Q, J, 9 kickers"
+        );
+        assert_eq!(
+            describe("Js7c6d5c4c3c2c"),
+            "Flush, Seven high with 5, 4, 3, 2 kickers"
+        );
+        assert_eq!(describe("AhKcKdKhQcJdTs"), "Straight, Ace high");
+        assert_eq!(describe("Ac8c7c5d4d3d2d"), "Straight, Five high");
+        assert_eq!(
+            describe("AsAcAhKhQd5c3s"),
+            "Three of a Kind, Aces with K, Q kickers"
+        );
+        assert_eq!(
+            describe("7d5c4c3c2d2s2h"),
+            "Three of a Kind, Twos with 7, 5 kickers"
+        );
+        assert_eq!(
+            describe("AsAhKsKhQsQhJs"),
+            "Two Pair, Aces and Kings with Q kicker"
+        );
+        assert_eq!(
+            describe("7c6d5h3s3c2d2h"),
+            "Two Pair, Threes and Twos with 7 kicker"
+        );
+        assert_eq!(
+            describe("AdAsKhQdJs3s2c"),
+            "One Pair, Aces with K, Q, J kickers"
+        );
+        assert_eq!(
+            describe("8s7s5h4c3c2d2c"),
+            "One Pair, Twos with 8, 7, 5 kickers"
+        );
+        assert_eq!(
+            describe("AdKdQdJd9s3h2c"),
+            "High Card, Ace high with K, Q, J, 9 kickers"
+        );
+        assert_eq!(
+            describe("9h8s7d5d4d3c2d"),
+            "High Card, Nine high with 8, 7, 5, 4 kickers"
+        );
+    }
+
+    #[test]
+    fn test_poker_hand_name() {
+        let name = |hand_str: &str| poker_hand_name(hand_str.parse::<Hand>().unwrap().evaluate());
+        assert_eq!(poker_hand_name((8 << 12) + 9), "Royal Flush");
+        assert_eq!(poker_hand_name((8 << 12) + 0), "Steel Wheel");
+        assert_eq!(poker_hand_name((8 << 12) + 1), "Six-high Straight Flush");
+        assert_eq!(poker_hand_name((8 << 12) + 8), "King-high Straight Flush");
+        assert_eq!(name("AhKcQdJhTs"), "Broadway");
+        assert_eq!(name("Ac2d3h4s5s"), "Wheel");
+        assert_eq!(name("6c2d3h4s5s"), "Six-high Straight");
+        assert_eq!(name("AsAcAhAdKs"), "Four Aces");
+        assert_eq!(name("AsAdKhKcKd"), "Kings Full");
+        assert_eq!(name("AhKhQhJh9h"), "Ace-high Flush");
+        assert_eq!(name("7c5c4c3c2c"), "Seven-high Flush");
+        assert_eq!(name("3s3c3hKhQd"), "Three Threes");
+        assert_eq!(name("AsAhKsKhQs"), "Aces Up");
+        assert_eq!(name("5s5hAs3h2d"), "Pair of Fives");
+        assert_eq!(name("AdKd9s3h2c"), "Ace High");
+        assert_eq!(name("7h5s4d3c2d"), "Seven High");
+
+        // every name is reachable, and agrees with the full description
+        let mut names = HashSet::new();
+        for (category, size) in CATEGORY_SIZES.iter().enumerate() {
+            for index in 0..*size {
+                let hand_rank = ((category as u16) << 12) + index;
+                let name = poker_hand_name(hand_rank);
+                if category == HandCategory::HighCard as usize {
+                    let high = name.trim_end_matches(" High");
+                    let description = get_hand_description(hand_rank);
+                    assert!(description.starts_with(&format!("High Card, {} high", high)));
+                }
+                names.insert(name);
+            }
+        }
+        let total = HAND_NAMES
+            .iter()
+            .map(|(_, names)| names.len())
+            .sum::<usize>();
+        assert_eq!(names.len(), total + 4);
+    }
+
+    #[test]
+    fn test_all_hand_descriptions() {
+        let mut descriptions = HashSet::new();
+        for (category, size) in CATEGORY_SIZES.iter().enumerate() {
+            for index in 0..*size {
+                let description = get_hand_description(((category as u16) << 12) + index);
+                assert!(!description.is_empty());
+                descriptions.insert(description);
+            }
+        }
+        assert_eq!(descriptions.len(), 7462);
+    }
+}
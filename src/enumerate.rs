@@ -1,5 +1,8 @@
+use crate::card::*;
+use crate::card_set::*;
 use crate::hand::*;
 use assets::constants::*;
+use std::convert::TryFrom;
 
 const NUM_HAND_CATEGORIES: usize = HandCategory::StraightFlush as usize + 1;
 
@@ -20,17 +23,17 @@ pub fn enumerate_hand_category(hand: &Hand, dead_cards: &Hand) -> [u32; NUM_HAND
     }
 }
 
-fn compute_alive_cards(mask: u64) -> Vec<usize> {
+fn compute_alive_cards(mask: u64) -> Vec<Card> {
     let mut result = Vec::new();
     for i in 0..NUMBER_OF_CARDS {
         if (CARDS[i].1 & mask) == 0 {
-            result.push(i);
+            result.push(Card::try_from(i).unwrap());
         }
     }
     result
 }
 
-fn enumerate_hand_category_2(hand: &Hand, alive_cards: &[usize]) -> [u32; NUM_HAND_CATEGORIES] {
+fn enumerate_hand_category_2(hand: &Hand, alive_cards: &[Card]) -> [u32; NUM_HAND_CATEGORIES] {
     let len = alive_cards.len();
     let mut result = [0; NUM_HAND_CATEGORIES];
     for i in 0..(len - 4) {
@@ -52,7 +55,7 @@ fn enumerate_hand_category_2(hand: &Hand, alive_cards: &[usize]) -> [u32; NUM_HA
     result
 }
 
-fn enumerate_hand_category_3(hand: &Hand, alive_cards: &[usize]) -> [u32; NUM_HAND_CATEGORIES] {
+fn enumerate_hand_category_3(hand: &Hand, alive_cards: &[Card]) -> [u32; NUM_HAND_CATEGORIES] {
     let len = alive_cards.len();
     let mut result = [0; NUM_HAND_CATEGORIES];
     for i in 0..(len - 3) {
@@ -71,7 +74,7 @@ fn enumerate_hand_category_3(hand: &Hand, alive_cards: &[usize]) -> [u32; NUM_HA
     result
 }
 
-fn enumerate_hand_category_4(hand: &Hand, alive_cards: &[usize]) -> [u32; NUM_HAND_CATEGORIES] {
+fn enumerate_hand_category_4(hand: &Hand, alive_cards: &[Card]) -> [u32; NUM_HAND_CATEGORIES] {
     let len = alive_cards.len();
     let mut result = [0; NUM_HAND_CATEGORIES];
     for i in 0..(len - 2) {
@@ -87,7 +90,7 @@ fn enumerate_hand_category_4(hand: &Hand, alive_cards: &[usize]) -> [u32; NUM_HA
     result
 }
 
-fn enumerate_hand_category_5(hand: &Hand, alive_cards: &[usize]) -> [u32; NUM_HAND_CATEGORIES] {
+fn enumerate_hand_category_5(hand: &Hand, alive_cards: &[Card]) -> [u32; NUM_HAND_CATEGORIES] {
     let len = alive_cards.len();
     let mut result = [0; NUM_HAND_CATEGORIES];
     for i in 0..(len - 1) {
@@ -100,7 +103,7 @@ fn enumerate_hand_category_5(hand: &Hand, alive_cards: &[usize]) -> [u32; NUM_HA
     result
 }
 
-fn enumerate_hand_category_6(hand: &Hand, alive_cards: &[usize]) -> [u32; NUM_HAND_CATEGORIES] {
+fn enumerate_hand_category_6(hand: &Hand, alive_cards: &[Card]) -> [u32; NUM_HAND_CATEGORIES] {
     let len = alive_cards.len();
     let mut result = [0; NUM_HAND_CATEGORIES];
     for i in 0..len {
@@ -110,8 +113,98 @@ fn enumerate_hand_category_6(hand: &Hand, alive_cards: &[usize]) -> [u32; NUM_HA
     result
 }
 
-fn enumerate_hand_category_7(hand: &Hand, _: &[usize]) -> [u32; NUM_HAND_CATEGORIES] {
+fn enumerate_hand_category_7(hand: &Hand, _: &[Card]) -> [u32; NUM_HAND_CATEGORIES] {
     let mut result = [0; NUM_HAND_CATEGORIES];
     result[get_hand_category(hand.evaluate()) as usize] += 1;
     result
 }
+
+impl Hand {
+    /// Computes the probability that `self` (0-5 cards) falls into `category` when completed to
+    /// 5 cards with the cards of `deck_remaining`.
+    /// All completions are enumerated, and the cards of `self` in `deck_remaining` are ignored.
+    pub fn probability_of_category(&self, category: HandCategory, deck_remaining: CardSet) -> f64 {
+        assert!(self.len() <= 5);
+        let alive_cards = compute_alive_cards(!deck_remaining.get_hand_mask() | self.get_mask());
+        assert!(alive_cards.len() >= 5 - self.len());
+        let (hits, total) = count_category(self, &alive_cards, 5 - self.len(), category);
+        hits as f64 / total as f64
+    }
+}
+
+/// Counts the completions of `hand` with `n` of `alive_cards` that fall into `category`.
+/// Return value: (# of completions in `category`, # of all completions)
+fn count_category(
+    hand: &Hand,
+    alive_cards: &[Card],
+    n: usize,
+    category: HandCategory,
+) -> (u64, u64) {
+    if n == 0 {
+        return ((get_hand_category(hand.evaluate()) == category) as u64, 1);
+    }
+    let mut result = (0, 0);
+    for i in 0..=(alive_cards.len() - n) {
+        let tmp = count_category(
+            &hand.add_card(alive_cards[i]),
+            &alive_cards[(i + 1)..],
+            n - 1,
+            category,
+        );
+        result.0 += tmp.0;
+        result.1 += tmp.1;
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FULL_DECK: CardSet = CardSet((1 << NUMBER_OF_CARDS) - 1);
+
+    #[test]
+    fn test_probability_of_category() {
+        let total = 2598960.0;
+        let probability =
+            Hand::new().probability_of_category(HandCategory::StraightFlush, FULL_DECK);
+        assert!((probability - 40.0 / total).abs() < 1e-12);
+
+        let hand = "AsKs".parse::<Hand>().unwrap();
+        let total = 19600.0;
+        for (category, count) in &[
+            (HandCategory::StraightFlush, 1.0),
+            (HandCategory::Flush, 164.0),
+            (HandCategory::Straight, 63.0),
+            (HandCategory::HighCard, 10332.0),
+        ] {
+            let probability = hand.probability_of_category(*category, FULL_DECK);
+            assert!(
+                (probability - count / total).abs() < 1e-12,
+                "{:?}",
+                category
+            );
+        }
+        let sum = (0..9)
+            .map(|i| HandCategory::try_from(i).unwrap())
+            .map(|category| hand.probability_of_category(category, FULL_DECK))
+            .sum::<f64>();
+        assert!((sum - 1.0).abs() < 1e-12);
+
+        // removed cards are not dealt, and a complete hand has probability zero or one
+        let deck = FULL_DECK.difference(CardSet::from("Qs".parse::<Hand>().unwrap()));
+        assert_eq!(
+            hand.probability_of_category(HandCategory::StraightFlush, deck),
+            0.0
+        );
+        let royal = "AsKsQsJsTs".parse::<Hand>().unwrap();
+        assert_eq!(
+            royal.probability_of_category(HandCategory::StraightFlush, CardSet::new()),
+            1.0
+        );
+        assert_eq!(
+            royal.probability_of_category(HandCategory::Flush, CardSet::new()),
+            0.0
+        );
+    }
+}
@@ -1,9 +1,18 @@
+use crate::card::*;
+use crate::card_set::*;
+use crate::combinations::*;
 use assets::constants::*;
 use assets::lookup::{LOOKUP, LOOKUP_FLUSH};
 use assets::offsets::OFFSETS;
-use std::ops::{Add, AddAssign};
+use std::cmp::Ordering;
+use std::convert::{TryFrom, TryInto};
+use std::fmt;
+use std::ops::{Add, AddAssign, Deref, RangeInclusive, Sub, SubAssign};
 use std::str::FromStr;
 
+#[cfg(feature = "rand")]
+use rand::Rng;
+
 #[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub enum HandCategory {
     HighCard = 0,
@@ -17,6 +26,19 @@ pub enum HandCategory {
     StraightFlush = 8,
 }
 
+/// number of distinct hand ranks in each hand category
+pub(crate) const CATEGORY_SIZES: [u16; 9] = [1277, 2860, 858, 858, 10, 1277, 156, 156, 10];
+
+impl HandCategory {
+    /// Returns the range of hand ranks computed by `Hand::evaluate()` that belong to `self`.
+    /// Every value in the range is assigned to some 5-card hand.
+    #[inline]
+    pub fn rank_range(&self) -> RangeInclusive<u16> {
+        let base = (*self as u16) << 12;
+        base..=(base + CATEGORY_SIZES[*self as usize] - 1)
+    }
+}
+
 /// Returns the hand category from hand rank computed by `Hand::evaluate()`.
 #[inline]
 pub fn get_hand_category(hand_rank: u16) -> HandCategory {
@@ -34,7 +56,78 @@ pub fn get_hand_category(hand_rank: u16) -> HandCategory {
     }
 }
 
-#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+/// Converts a numeric value (0-8) obtained by `u8::from()` back to the hand category.
+impl TryFrom<u8> for HandCategory {
+    type Error = ();
+
+    #[inline]
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(HandCategory::HighCard),
+            1 => Ok(HandCategory::OnePair),
+            2 => Ok(HandCategory::TwoPair),
+            3 => Ok(HandCategory::ThreeOfAKind),
+            4 => Ok(HandCategory::Straight),
+            5 => Ok(HandCategory::Flush),
+            6 => Ok(HandCategory::FullHouse),
+            7 => Ok(HandCategory::FourOfAKind),
+            8 => Ok(HandCategory::StraightFlush),
+            _ => Err(()),
+        }
+    }
+}
+
+impl From<HandCategory> for u8 {
+    #[inline]
+    fn from(category: HandCategory) -> Self {
+        category as u8
+    }
+}
+
+/// bit mask of the valid card positions in `Hand::mask`
+const VALID_MASK: u64 = 0x1fff_1fff_1fff_1fff;
+
+/// Error type for constructing a `Hand` from bit masks.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum HandError {
+    /// more than 7 cards are specified
+    TooManyCards(usize),
+    /// card index is not in the range \[0, 51\]
+    InvalidCardIndex(usize),
+    /// mask has bits set outside the card positions of `Hand::get_mask()`
+    InvalidMask(u64),
+    /// card is already included in the hand
+    DuplicateCard(Card),
+    /// card is not included in the hand
+    CardNotPresent(Card),
+    /// string is not a valid list of cards
+    InvalidFormat,
+}
+
+impl fmt::Display for HandError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            HandError::TooManyCards(len) => write!(f, "too many cards: {} (must be 0-7)", len),
+            HandError::InvalidCardIndex(index) => {
+                write!(f, "invalid card index: {} (must be 0-51)", index)
+            }
+            HandError::InvalidMask(mask) => write!(f, "invalid mask: {:#x}", mask),
+            HandError::DuplicateCard(card) => write!(f, "duplicate card: {}", card),
+            HandError::CardNotPresent(card) => write!(f, "card not present: {}", card),
+            HandError::InvalidFormat => write!(f, "invalid format"),
+        }
+    }
+}
+
+impl std::error::Error for HandError {}
+
+/// A hand of at most 7 cards.
+///
+/// Comparison operators (`<`, `>`, etc.) compare hand strengths; see the `PartialOrd` impl.
+/// Different hands of the same strength are incomparable: `a < b`, `a > b`, `a <= b` and
+/// `a >= b` are all false, so `sort_by(|a, b| a.partial_cmp(b).unwrap())` panics on tied hands
+/// (e.g., a split pot). Sort by `evaluate()` or use `beats()` instead.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 pub struct Hand {
     key: u64,
     mask: u64,
@@ -50,188 +143,2672 @@ impl Hand {
         }
     }
 
+    /// Creates an empty `Hand` struct; identical to `Hand::new()`.
+    /// `Hand` is a pair of 64-bit integers and never allocates, so construction and adding cards
+    /// are always O(1) and no capacity needs to be reserved. `_n` (the expected number of cards)
+    /// is accepted only for readability at call sites.
+    #[inline]
+    pub fn with_cards(_n: usize) -> Self {
+        Self::new()
+    }
+
+    /// Creates a new hand of `n` distinct cards chosen uniformly at random.
+    /// This function panics when `n > 7`.
+    #[cfg(feature = "rand")]
+    pub fn deal_random<R: Rng + ?Sized>(n: usize, rng: &mut R) -> Self {
+        assert!(n <= 7);
+        let mut deck = [0; NUMBER_OF_CARDS];
+        for (i, index) in deck.iter_mut().enumerate() {
+            *index = i;
+        }
+        // partial Fisher-Yates shuffle
+        let mut hand = Self::new();
+        for i in 0..n {
+            deck.swap(i, rng.gen_range(i..NUMBER_OF_CARDS));
+            hand = hand.add_card(Card::try_from(deck[i]).unwrap());
+        }
+        hand
+    }
+
     /// Creates a new hand structure consists of `cards`.
-    /// Elements in `cards` must be in the range \[0, 51\].
-    /// (0 corresponds to the deuce of clubs, and 51 corresponds to the ace of spades)
     #[inline]
-    pub fn from_slice(cards: &[usize]) -> Self {
+    pub fn from_slice(cards: &[Card]) -> Self {
         let mut hand = Self::new();
         for card in cards {
             hand = hand.add_card(*card);
         }
-        hand
+        hand
+    }
+
+    /// Reconstructs a hand from its bit mask obtained by `get_mask()`, whose layout is internal
+    /// and differs from the card-index layout of `from_u64_mask()`.
+    /// Fails when `mask` has any bit outside the card positions or more than 7 bits set.
+    #[inline]
+    pub fn from_internal_mask(mask: u64) -> Result<Self, HandError> {
+        if (mask & !VALID_MASK) != 0 {
+            return Err(HandError::InvalidMask(mask));
+        }
+        if mask.count_ones() > 7 {
+            return Err(HandError::TooManyCards(mask.count_ones() as usize));
+        }
+        Ok(Cards { mask }.fold(Self::new(), |hand, card| hand.add_card(card)))
+    }
+
+    /// Creates a new hand from `mask`, where bit `i` represents the card of index `i`.
+    /// Fails when any bit above 51 is set or more than 7 bits are set.
+    pub fn from_u64_mask(mask: u64) -> Result<Self, HandError> {
+        if (mask >> NUMBER_OF_CARDS) != 0 {
+            let index = 63 - mask.leading_zeros() as usize;
+            return Err(HandError::InvalidCardIndex(index));
+        }
+        if mask.count_ones() > 7 {
+            return Err(HandError::TooManyCards(mask.count_ones() as usize));
+        }
+        let mut hand = Self::new();
+        for i in 0..NUMBER_OF_CARDS {
+            if (mask >> i) & 1 != 0 {
+                hand = hand.add_card(Card::try_from(i).unwrap());
+            }
+        }
+        Ok(hand)
+    }
+
+    /// Creates a new hand from `fen`, a list of cards delimited by slashes (e.g., "As/Kd/Qh"),
+    /// delimited by commas (e.g., "As,Kd,Qh"), or concatenated without delimiters (e.g.,
+    /// "AsKdQh"). Whitespace around the cards is ignored, but mixing delimiters is not allowed.
+    // `usize::is_multiple_of()` requires Rust 1.87
+    #[allow(clippy::manual_is_multiple_of)]
+    pub fn from_fen(fen: &str) -> Result<Self, HandError> {
+        let fen = fen.trim();
+        if fen.is_empty() {
+            return Ok(Self::new());
+        }
+        let tokens = match (fen.contains('/'), fen.contains(',')) {
+            (true, true) => return Err(HandError::InvalidFormat),
+            (true, false) => fen.split('/').map(str::trim).collect::<Vec<_>>(),
+            (false, true) => fen.split(',').map(str::trim).collect::<Vec<_>>(),
+            (false, false) if fen.is_ascii() && fen.len() % 2 == 0 => (0..fen.len())
+                .step_by(2)
+                .map(|i| &fen[i..(i + 2)])
+                .collect(),
+            (false, false) => return Err(HandError::InvalidFormat),
+        };
+        let mut hand = Self::new();
+        for token in tokens {
+            let card = token
+                .parse::<Card>()
+                .map_err(|_| HandError::InvalidFormat)?;
+            hand = hand.try_add_card(card.index())?;
+        }
+        Ok(hand)
+    }
+
+    /// Returns the cards of `self` delimited by slashes in ascending order of card index
+    /// (e.g., "Qh/Kd/As"), which can be parsed back by `from_fen()`.
+    pub fn to_fen(&self) -> String {
+        self.cards()
+            .map(|card| card.to_string())
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+
+    /// Checks whether the hand is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.mask == 0
+    }
+
+    /// Returns current number of cards in `self`.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.mask.count_ones() as usize
+    }
+
+    /// Checks whether the internal representation of `self` is consistent: `self` must consist of
+    /// at most 7 distinct cards, and its key (rank sums and suit counters) must agree with its
+    /// bit mask. Every hand built by the normal construction paths satisfies this, whereas adding
+    /// the same card twice or removing an absent card breaks it.
+    pub fn is_valid(&self) -> bool {
+        Self::from_internal_mask(self.mask) == Ok(*self)
+    }
+
+    /// Returns the bit mask of `self`.
+    #[inline]
+    pub fn get_mask(&self) -> u64 {
+        self.mask
+    }
+
+    /// Returns the key of `self` (the sum of card keys used for the lookup).
+    #[inline]
+    pub(crate) fn get_key(&self) -> u64 {
+        self.key
+    }
+
+    /// Creates a hand directly from a `key` and `mask` pair without any check. The result may be
+    /// inconsistent and must be checked with `is_valid()`; intended only for fuzzing.
+    #[doc(hidden)]
+    #[inline]
+    pub fn from_raw_parts(key: u64, mask: u64) -> Self {
+        Self { key, mask }
+    }
+
+    /// Returns an iterator over the cards in `self` in ascending order of card index.
+    #[inline]
+    pub fn cards(&self) -> Cards {
+        Cards { mask: self.mask }
+    }
+
+    /// Returns the cards in `self` as a fixed-capacity array, in ascending order of card index.
+    #[inline]
+    pub fn to_card_array(&self) -> CardArray {
+        let mut result = CardArray {
+            cards: [Card::try_from(0).unwrap(); 7],
+            len: 0,
+        };
+        for card in self.cards().take(7) {
+            result.cards[result.len] = card;
+            result.len += 1;
+        }
+        result
+    }
+
+    /// Returns the hand rebuilt from the cards of `self` in ascending order of card index.
+    /// Since both the key and the bit mask are sums of per-card values, the insertion order never
+    /// affects the representation, and thus the result equals `self` for every valid hand.
+    #[inline]
+    pub fn normalize(&self) -> Self {
+        Self::from_slice(&self.to_card_array())
+    }
+
+    /// Returns a new hand struct where the suits of `self` are cyclically permuted
+    /// (spades to hearts, hearts to clubs, clubs to diamonds, and diamonds to spades).
+    /// The result has the same strength as `self`; applying this function four times yields `self`.
+    #[inline]
+    pub fn flip_suits(&self) -> Self {
+        self.permute_suits([1, 2, 3, 0])
+    }
+
+    /// Returns the suit-normalized representation of `self`: among all hands obtained by
+    /// permuting the suits of `self`, the one with the smallest `get_mask()` value.
+    /// Two hands are suit-isomorphic (strategically equivalent) if and only if they have the same
+    /// canonical form.
+    pub fn canonical_form(&self) -> Self {
+        let mut best = *self;
+        for a in 0..4 {
+            for b in 0..4 {
+                for c in 0..4 {
+                    for d in 0..4 {
+                        if (1 << a) | (1 << b) | (1 << c) | (1 << d) != 0b1111 {
+                            continue;
+                        }
+                        let hand = self.permute_suits([a, b, c, d]);
+                        if hand.mask < best.mask {
+                            best = hand;
+                        }
+                    }
+                }
+            }
+        }
+        best
+    }
+
+    /// Returns four sub-hands of `self`, each consisting of the cards of a single suit, in the suit
+    /// order of `Card::suit()` (spades, hearts, clubs and diamonds).
+    pub fn split_suits(&self) -> [Self; 4] {
+        [3, 2, 0, 1]
+            .map(|group| Self::from_internal_mask(self.mask & (0x1fff << (16 * group))).unwrap())
+    }
+
+    /// Returns the number of cards of each suit in `self`, in the suit order of `Card::suit()`
+    /// (spades, hearts, clubs and diamonds).
+    #[inline]
+    pub fn suit_distribution(&self) -> [u8; 4] {
+        // each suit nibble of the key starts from 3 (see `Hand::new()`)
+        [0, 1, 3, 2].map(|nibble| ((self.key >> (SUIT_SHIFT + 4 * nibble)) & 0xf) as u8 - 3)
+    }
+
+    /// Returns whether no two cards of `self` share a suit. Returns `false` when `self` is empty.
+    #[inline]
+    pub fn is_rainbow(&self) -> bool {
+        !self.is_empty() && self.suit_distribution().iter().all(|count| *count <= 1)
+    }
+
+    /// Returns whether all cards of `self` are of the same suit. Returns `false` when `self` is
+    /// empty.
+    #[inline]
+    pub fn is_monotone(&self) -> bool {
+        self.num_suits() == 1
+    }
+
+    /// Returns whether the cards of `self` are of exactly two suits.
+    #[inline]
+    pub fn is_two_tone(&self) -> bool {
+        self.num_suits() == 2
+    }
+
+    /// Returns the number of suits present in `self`.
+    #[inline]
+    fn num_suits(&self) -> usize {
+        self.suit_distribution()
+            .iter()
+            .filter(|count| **count > 0)
+            .count()
+    }
+
+    /// Returns an iterator over all pairs of card indices `(a, b)` with `a < b` in `self` such that
+    /// the two cards share a suit.
+    #[inline]
+    pub fn suited_cards(&self) -> impl Iterator<Item = (usize, usize)> {
+        self.card_pairs()
+            .filter(|(c0, c1)| c0.suit() == c1.suit())
+            .map(|(c0, c1)| (c0.index(), c1.index()))
+    }
+
+    /// Returns an iterator over all pairs of distinct cards in `self`, in ascending order of card
+    /// index (also within each pair).
+    #[inline]
+    fn card_pairs(&self) -> impl Iterator<Item = (Card, Card)> {
+        let cards = self.to_card_array();
+        (0..cards.len())
+            .flat_map(move |i| ((i + 1)..cards.len()).map(move |j| (cards[i], cards[j])))
+    }
+
+    /// Returns a new hand struct where each card of suit `s` in `self` is replaced with the card
+    /// of the same rank and suit `perm[s]`.
+    fn permute_suits(&self, perm: [u8; 4]) -> Self {
+        self.cards().fold(Self::new(), |hand, card| {
+            hand.add_card(Card::new(card.rank(), perm[card.suit() as usize]).unwrap())
+        })
+    }
+
+    /// Returns whether the `card` is included in `self`.
+    #[inline]
+    pub fn contains(&self, card: Card) -> bool {
+        (self.mask & unsafe { *CARDS.get_unchecked(card.index()) }.1) != 0
+    }
+
+    /// Returns whether any card of `blocker` is included in `self`.
+    /// This is useful for blocker analysis; e.g., holding the ace of a suit blocks the nut flush of
+    /// that suit, which is checked by `nut_flush_combo.is_blocked_by(hole)`.
+    #[inline]
+    pub fn is_blocked_by(&self, blocker: Hand) -> bool {
+        (self.mask & blocker.mask) != 0
+    }
+
+    /// Returns a new hand struct consisting of the cards included in both `self` and `other`.
+    #[inline]
+    pub fn shared_cards(&self, other: &Hand) -> Self {
+        Self::from_internal_mask(self.mask & other.mask).unwrap()
+    }
+
+    /// Returns whether any card of `rank` (0 corresponds to the deuce, and 12 corresponds to the ace)
+    /// is included in `self`.
+    #[inline]
+    pub fn contains_rank(&self, rank: u8) -> bool {
+        self.count_rank(rank) > 0
+    }
+
+    /// Returns the number of cards of `rank` (0 corresponds to the deuce, and 12 corresponds to the
+    /// ace) in `self`.
+    #[inline]
+    pub fn count_rank(&self, rank: u8) -> u8 {
+        assert!((rank as usize) < NUMBER_OF_RANKS);
+        ((self.mask >> rank) & 0x0001_0001_0001_0001).count_ones() as u8
+    }
+
+    /// Returns the number of cards of each rank in `self`, indexed by rank (0 corresponds to the
+    /// deuce, and 12 corresponds to the ace).
+    #[inline]
+    pub fn rank_distribution(&self) -> [u8; NUMBER_OF_RANKS] {
+        let mut distribution = [0; NUMBER_OF_RANKS];
+        for rank in 0..NUMBER_OF_RANKS {
+            distribution[rank] = self.count_rank(rank as u8);
+        }
+        distribution
+    }
+
+    /// Returns an iterator over all pairs of card indices `(a, b)` with `a < b` in `self` such that
+    /// the ranks of the two cards differ by one. The ace is adjacent only to the king.
+    #[inline]
+    pub fn connected_cards(&self) -> impl Iterator<Item = (usize, usize)> {
+        self.card_pairs()
+            .filter(|(c0, c1)| c0.rank() + 1 == c1.rank() || c1.rank() + 1 == c0.rank())
+            .map(|(c0, c1)| (c0.index(), c1.index()))
+    }
+
+    /// Returns the rank of the highest card in `self` (0 corresponds to the deuce, and 12
+    /// corresponds to the ace).
+    /// This function panics when `self` is empty.
+    #[inline]
+    pub fn high_card_rank(&self) -> u8 {
+        assert!(!self.is_empty());
+        (63 - self.rankset().leading_zeros()) as u8
+    }
+
+    /// Returns the rank of the lowest card in `self` (0 corresponds to the deuce, and 12
+    /// corresponds to the ace). Aces are always treated as high.
+    /// This function panics when `self` is empty.
+    #[inline]
+    pub fn low_card_rank(&self) -> u8 {
+        assert!(!self.is_empty());
+        self.rankset().trailing_zeros() as u8
+    }
+
+    /// Returns how many more cards of a single suit are needed to make a flush, counting from the
+    /// suit with the most cards in `self`. Returns `None` when `self` already contains a flush.
+    #[inline]
+    pub fn missing_cards_to_flush(&self) -> Option<u8> {
+        let max_suit_count = (0..4)
+            .map(|suit| ((self.mask >> (16 * suit)) & 0xffff).count_ones())
+            .max()
+            .unwrap();
+        if max_suit_count >= 5 {
+            None
+        } else {
+            Some(5 - max_suit_count as u8)
+        }
+    }
+
+    /// Returns the minimum number of additional ranks needed to make a straight, over the 10
+    /// straights from A-2-3-4-5 to T-J-Q-K-A. Returns `None` when `self` already contains a
+    /// straight. Note that open-ended draws and inside (gutshot) draws both return `Some(1)`.
+    pub fn missing_cards_to_straight(&self) -> Option<u8> {
+        let rankset = with_low_ace(self.rankset());
+        let max_count = (0..10)
+            .map(|low| ((rankset >> low) & 0x1f).count_ones())
+            .max()
+            .unwrap();
+        if max_count == 5 {
+            None
+        } else {
+            Some(5 - max_count as u8)
+        }
+    }
+
+    /// Returns whether `self` contains two cards of the same rank.
+    /// Unlike `evaluate()`, this function and the other `has_*()` predicates take any number of
+    /// cards and do not consult the lookup tables.
+    #[inline]
+    pub fn has_pair(&self) -> bool {
+        self.rank_multiplicities()[0] != 0
+    }
+
+    /// Returns whether `self` contains two pairs of different ranks (a full house counts).
+    #[inline]
+    pub fn has_two_pair(&self) -> bool {
+        self.rank_multiplicities()[0].count_ones() >= 2
+    }
+
+    /// Returns whether `self` contains three cards of the same rank.
+    #[inline]
+    pub fn has_trips(&self) -> bool {
+        self.rank_multiplicities()[1] != 0
+    }
+
+    /// Returns whether `self` contains four cards of the same rank.
+    #[inline]
+    pub fn has_quads(&self) -> bool {
+        self.rank_multiplicities()[2] != 0
+    }
+
+    /// Returns whether `self` contains five cards of consecutive ranks (A-2-3-4-5 counts).
+    #[inline]
+    pub fn has_straight(&self) -> bool {
+        let r = with_low_ace(self.rankset());
+        (r & (r >> 1) & (r >> 2) & (r >> 3) & (r >> 4)) != 0
+    }
+
+    /// Returns whether `self` contains five cards of the same suit.
+    #[inline]
+    pub fn has_flush(&self) -> bool {
+        (self.key & FLUSH_MASK) != 0
+    }
+
+    /// Returns the rank set of `self`, where bit `r` is set when `self` contains a card of rank
+    /// `r` (0 corresponds to the deuce, and 12 corresponds to the ace).
+    #[inline]
+    pub(crate) fn rankset(&self) -> u64 {
+        mask_rankset(self.mask)
+    }
+
+    /// Returns the rank sets of the ranks appearing at least twice, at least three times and
+    /// four times in `self`.
+    /// The `has_*()` predicates use this instead of `rank_distribution()`, which counts the cards
+    /// of each rank one by one: the four suit rows of the mask are combined with a few bitwise
+    /// operations, so the predicates stay branch-free and cheaper than `evaluate()`.
+    #[inline]
+    fn rank_multiplicities(&self) -> [u64; 3] {
+        let m = self.mask;
+        let (a, b, c, d) = (m & 0x1fff, (m >> 16) & 0x1fff, (m >> 32) & 0x1fff, m >> 48);
+        [
+            (a & b) | (a & c) | (a & d) | (b & c) | (b & d) | (c & d),
+            (a & b & c) | (a & b & d) | (a & c & d) | (b & c & d),
+            a & b & c & d,
+        ]
+    }
+
+    /// Returns a new hand struct where `card` is added to `self`.
+    /// `card` must not be already included in `self`.
+    #[inline]
+    pub fn add_card(&self, card: Card) -> Self {
+        unsafe { self.add_card_unchecked(card.index()) }
+    }
+
+    /// Returns a new hand struct where the card of index `card` is added to `self`.
+    /// Unlike `add_card()`, fails when `card` is out of range, already included in `self`, or
+    /// `self` already has 7 cards.
+    #[inline]
+    pub fn try_add_card(&self, card: usize) -> Result<Self, HandError> {
+        let card = Card::try_from(card).map_err(|_| HandError::InvalidCardIndex(card))?;
+        if self.contains(card) {
+            return Err(HandError::DuplicateCard(card));
+        }
+        if self.len() == 7 {
+            return Err(HandError::TooManyCards(8));
+        }
+        Ok(self.add_card(card))
+    }
+
+    /// Returns a new hand struct where `card` is removed from `self`.
+    /// `card` must be included in `self`.
+    #[inline]
+    pub fn remove_card(&self, card: Card) -> Self {
+        unsafe { self.remove_card_unchecked(card.index()) }
+    }
+
+    /// Returns a new hand struct where the card of index `card` is removed from `self`.
+    /// Unlike `remove_card()`, fails when `card` is out of range or not included in `self`.
+    #[inline]
+    pub fn try_remove_card(&self, card: usize) -> Result<Self, HandError> {
+        let card = Card::try_from(card).map_err(|_| HandError::InvalidCardIndex(card))?;
+        if !self.contains(card) {
+            return Err(HandError::CardNotPresent(card));
+        }
+        Ok(self.remove_card(card))
+    }
+
+    /// Returns a new hand struct where `cards` are removed from `self`.
+    /// All of `cards` must be included in `self` (checked only in debug builds).
+    #[inline]
+    pub fn remove_cards(&self, cards: &[Card]) -> Self {
+        let mut hand = *self;
+        for card in cards {
+            debug_assert!(hand.contains(*card), "{} is not included in the hand", card);
+            hand = hand.remove_card(*card);
+        }
+        hand
+    }
+
+    /// Returns a new hand struct where the card of index `card` is added to `self`.
+    /// `card` must not be already included in `self`.
+    /// (0 corresponds to the deuce of spades, and 51 corresponds to the ace of diamonds)
+    ///
+    /// # Safety
+    /// `card` must be in the range \[0, 51\].
+    #[inline]
+    pub unsafe fn add_card_unchecked(&self, card: usize) -> Self {
+        let (k, m) = *CARDS.get_unchecked(card);
+        Self {
+            key: self.key.wrapping_add(k),
+            mask: self.mask.wrapping_add(m),
+        }
+    }
+
+    /// Returns a new hand struct where the card of index `card` is removed from `self`.
+    /// `card` must be included in `self`.
+    ///
+    /// # Safety
+    /// `card` must be in the range \[0, 51\].
+    #[inline]
+    pub unsafe fn remove_card_unchecked(&self, card: usize) -> Self {
+        let (k, m) = *CARDS.get_unchecked(card);
+        Self {
+            key: self.key.wrapping_sub(k),
+            mask: self.mask.wrapping_sub(m),
+        }
+    }
+
+    /// Returns hand strength in 16-bit integer.
+    /// This function may crush when `self.len() < 5 || self.len() > 7` or `!self.is_valid()`.
+    #[inline]
+    pub fn evaluate(&self) -> u16 {
+        evaluate_key_mask(self.key, self.mask)
+    }
+
+    /// Returns hand strength in 16-bit integer like `evaluate()`, or `None` when
+    /// `self.len() < 5 || self.len() > 7`.
+    #[inline]
+    pub fn evaluate_safe(&self) -> Option<u16> {
+        if 5 <= self.len() && self.len() <= 7 {
+            Some(self.evaluate())
+        } else {
+            None
+        }
+    }
+
+    /// Returns the strength of the best 5 cards out of the 7 cards of `self`.
+    /// This is the same as `evaluate()`, but states the intent explicitly.
+    /// This function panics in debug builds when `self.len() != 7`; release builds skip the check
+    /// and behave exactly like `evaluate()`.
+    #[inline]
+    pub fn evaluate_best_5_of_7(&self) -> u16 {
+        debug_assert_eq!(self.len(), 7);
+        self.evaluate()
+    }
+
+    /// Compares the strengths of `self` and `other`: returns `Ordering::Greater` when `self` is
+    /// the stronger hand, `Ordering::Less` when weaker, and `Ordering::Equal` on a tie.
+    /// This function may crush under the same conditions as `evaluate()`.
+    #[inline]
+    pub fn beats(&self, other: &Hand) -> Ordering {
+        self.evaluate().cmp(&other.evaluate())
+    }
+
+    /// Returns the strength within the hand category, i.e., the lower 12 bits of `evaluate()`.
+    /// This function may crush under the same conditions as `evaluate()`.
+    #[inline]
+    pub fn rank_in_category(&self) -> u16 {
+        self.evaluate() & 0x0fff
+    }
+
+    /// Returns the percentile (0-100) of the strength of `self` among all 7462 distinct hand
+    /// ranks, i.e., `100 * ordinal / 7462` where `ordinal` is the 0-based position of
+    /// `self.evaluate()` among the distinct ranks sorted in ascending order.
+    /// This function may crush under the same conditions as `evaluate()`.
+    #[inline]
+    pub fn rank_percentile(&self) -> f64 {
+        let rank = self.evaluate();
+        let category = (rank >> 12) as usize;
+        let ordinal = CATEGORY_SIZES[..category].iter().sum::<u16>() + (rank & 0x0fff);
+        ordinal as f64 / 7462.0 * 100.0
+    }
+
+    /// Returns the five cards that form the best hand in `self`, in ascending order of card index.
+    /// This function panics when `self.len() < 5 || self.len() > 7`.
+    pub fn best_five(&self) -> [Card; 5] {
+        assert!(5 <= self.len() && self.len() <= 7);
+        let rank = self.evaluate();
+        let best = HandCombinations::new(*self, 5)
+            .find(|five| five.evaluate() == rank)
+            .unwrap();
+        best.to_card_array().as_slice().try_into().unwrap()
+    }
+
+    /// Returns the bit mask (in the same layout as `get_mask()`) of the kicker cards, i.e., the cards
+    /// of `best_five()` that do not form the pairs, three of a kind or four of a kind.
+    /// For a high card hand, all cards but the highest one are kickers; straights, flushes and full
+    /// houses have no kickers because all five cards make the hand.
+    /// This function panics when `self.len() < 5 || self.len() > 7`.
+    pub fn kicker_mask(&self) -> u64 {
+        let best = Self::from_slice(&self.best_five());
+        let kickers = best
+            .cards()
+            .filter(|card| best.count_rank(card.rank()) == 1)
+            .collect::<Vec<_>>();
+        let kickers: &[Card] = match get_hand_category(best.evaluate()) {
+            HandCategory::HighCard => &kickers[..4],
+            HandCategory::OnePair
+            | HandCategory::TwoPair
+            | HandCategory::ThreeOfAKind
+            | HandCategory::FourOfAKind => &kickers,
+            _ => &[],
+        };
+        Self::from_slice(kickers).mask
+    }
+
+    /// Returns the strength of the best `n`-card sub-hand of `self`.
+    /// `n` must be in the range \[1, `self.len()`\] and `self.len()` must be at most 7.
+    ///
+    /// When `n` is 5-7, the sub-hands are evaluated with the lookup tables, so the return value
+    /// is the same as `evaluate()` of the best sub-hand. When `n` is 1-4, the lookup tables do not
+    /// apply; sub-hands are instead evaluated by their rank counts (straights and flushes are
+    /// impossible), and the return value is only comparable with those of other `n`-card hands.
+    /// `get_hand_category()` still works for the returned value in both cases.
+    pub fn evaluate_n(&self, n: usize) -> u16 {
+        let len = self.len();
+        assert!(1 <= n && n <= len && len <= 7);
+        if n == len && n >= 5 {
+            return self.evaluate();
+        }
+        let cards = self.cards().collect::<Vec<_>>();
+        let mut best = 0;
+        for subset in 0u32..(1 << len) {
+            if subset.count_ones() as usize != n {
+                continue;
+            }
+            let mut hand = Self::new();
+            for (i, card) in cards.iter().enumerate() {
+                if (subset >> i) & 1 != 0 {
+                    hand = hand.add_card(*card);
+                }
+            }
+            let rank = if n >= 5 {
+                hand.evaluate()
+            } else {
+                hand.evaluate_small()
+            };
+            best = best.max(rank);
+        }
+        best
+    }
+
+    /// Returns the strength of the best Omaha hand, where `self` is the board (3-5 cards) and
+    /// `hole` is the player's 4 hole cards.
+    /// Exactly 2 hole cards and exactly 3 board cards are used, so the return value may be lower
+    /// than `(*self + hole).evaluate()`. `self` and `hole` must not share any cards.
+    pub fn evaluate_omaha(&self, hole: Hand) -> u16 {
+        assert_eq!(hole.len(), 4);
+        let mut best = 0;
+        self.for_each_omaha_hand(hole, |five| best = best.max(five.evaluate()));
+        best
+    }
+
+    /// Returns the strengths of the best Omaha Hi-Lo (8-or-better) high and low hands, where
+    /// `self` is the board (3-5 cards) and `hole` is the player's 4 hole cards.
+    /// The high strength is the same as `evaluate_omaha()`. The low strength is `None` when no
+    /// combination of 2 hole cards and 3 board cards makes five unpaired cards of 8 or lower (aces
+    /// are low); otherwise, larger values are better lows, e.g., 224 for the nut low A-2-3-4-5
+    /// and 7 for 8-7-6-5-4.
+    pub fn evaluate_omaha_hi_lo(&self, hole: Hand) -> (u16, Option<u16>) {
+        assert_eq!(hole.len(), 4);
+        let mut high = 0;
+        let mut low = None;
+        self.for_each_omaha_hand(hole, |five| {
+            high = high.max(five.evaluate());
+            low = low.max(five.eight_or_better_low());
+        });
+        (high, low)
+    }
+
+    /// Returns the strength of the best 5-card PLO hand, where `self` is the board (3-5 cards) and
+    /// `hole` is the player's 5 hole cards.
+    /// As in `evaluate_omaha()`, exactly 2 hole cards and exactly 3 board cards are used.
+    pub fn evaluate_plo5(&self, hole: Hand) -> u16 {
+        assert_eq!(hole.len(), 5);
+        let mut best = 0;
+        self.for_each_omaha_hand(hole, |five| best = best.max(five.evaluate()));
+        best
+    }
+
+    /// Returns the strength of the best 5-card hand of Seven-Card Stud, where `self` is the
+    /// player's 7 cards. This is the same as `evaluate()` since there is no board.
+    #[inline]
+    pub fn evaluate_stud7(&self) -> u16 {
+        assert_eq!(self.len(), 7);
+        self.evaluate()
+    }
+
+    /// Returns the high strength and the 8-or-better low strength of Seven-Card Stud Hi-Lo,
+    /// where `self` is the player's 7 cards.
+    /// The high and low hands may use different 5 cards, and the low strength is `None` when no
+    /// five distinct ranks of A and 2-8 are available (see `evaluate_omaha_hi_lo()`).
+    pub fn evaluate_stud7_hi_lo(&self) -> (u16, Option<u16>) {
+        assert_eq!(self.len(), 7);
+        // bit 0 is the ace and bit 7 is the eight
+        let mut low_bits = with_low_ace(self.rankset()) & 0xff;
+        if low_bits.count_ones() < 5 {
+            return (self.evaluate(), None);
+        }
+        // the best low uses the five lowest ranks
+        while low_bits.count_ones() > 5 {
+            low_bits &= !(1 << (63 - low_bits.leading_zeros()));
+        }
+        (self.evaluate(), Some(0xff - low_bits as u16))
+    }
+
+    /// Calls `f` for every 5-card hand of exactly 2 cards of `hole` (4 or 5 cards) and 3 cards
+    /// of `self`.
+    fn for_each_omaha_hand<F: FnMut(Hand)>(&self, hole: Hand, mut f: F) {
+        assert!(3 <= self.len() && self.len() <= 5);
+        assert!(4 <= hole.len() && hole.len() <= 5);
+        assert_eq!(self.mask & hole.mask, 0);
+        for two in HandCombinations::new(hole, 2) {
+            for three in HandCombinations::new(*self, 3) {
+                f(two + three);
+            }
+        }
+    }
+
+    /// Computes the 8-or-better low strength of 5-card hand (see `evaluate_omaha_hi_lo()`).
+    fn eight_or_better_low(&self) -> Option<u16> {
+        let rankset = self.rankset();
+        // five distinct ranks of A and 2-8
+        if rankset.count_ones() != 5 || (rankset & !0x107f) != 0 {
+            return None;
+        }
+        // bit 0 is the ace and bit 7 is the eight
+        let low_bits = with_low_ace(rankset) & 0xff;
+        Some(0xff - low_bits as u16)
+    }
+
+    /// Returns the remaining cards that make `self` (hero's hole cards) beat `villain` when added
+    /// to `board` (3-4 cards), in ascending order of card index.
+    /// Only the cards that turn a loss or tie into a win are counted, so the result is empty when
+    /// hero is already winning. `self`, `villain` and `board` must not share any cards.
+    pub fn outs(&self, villain: Hand, board: Hand) -> Vec<Card> {
+        let (hero, villain, remaining) = self.out_candidates(villain, board);
+        remaining
+            .filter(|card| hero.add_card(*card).evaluate() > villain.add_card(*card).evaluate())
+            .collect()
+    }
+
+    /// Returns the number of outs, i.e., `self.outs(villain, board).len()`, without allocation.
+    pub fn count_outs(&self, villain: Hand, board: Hand) -> u8 {
+        let (hero, villain, remaining) = self.out_candidates(villain, board);
+        remaining
+            .filter(|card| hero.add_card(*card).evaluate() > villain.add_card(*card).evaluate())
+            .count() as u8
+    }
+
+    /// Returns the number of nut outs, i.e., the remaining cards that give `self` (hero's hole
+    /// cards) the best possible hand when added to `board` (3-4 cards): no two unknown cards can
+    /// make a stronger hand with the new board (ties still count as the nuts). The cards of
+    /// `dead` are neither dealt nor held by any opponent.
+    /// `self` and `board` must not share any cards.
+    ///
+    /// Note that holding the ace of a suit with three cards of that suit on the board usually
+    /// gives several nut outs, not one: every card of the suit completes the nut flush unless it
+    /// pairs the board or allows an opponent a straight flush. For example, `AsJd` on `Ks9s4s2h`
+    /// has 5 nut outs (`8s`, `7s`, `6s`, `5s` and `3s`).
+    pub fn count_nut_outs(&self, board: Hand, dead: CardSet) -> u8 {
+        assert!(3 <= board.len() && board.len() <= 4);
+        assert_eq!(self.len(), 2);
+        assert_eq!(self.mask & board.mask, 0);
+        let unknown = VALID_MASK & !(self.mask | board.mask | dead.get_hand_mask());
+        let mut count = 0;
+        for card in (Cards { mask: unknown }) {
+            let board = board.add_card(card);
+            let hero_rank = (*self + board).evaluate();
+            let others = Cards {
+                mask: unknown & !board.mask,
+            }
+            .collect::<Vec<_>>();
+            let is_nuts = others.iter().enumerate().all(|(i, c0)| {
+                let villain = board.add_card(*c0);
+                others[(i + 1)..]
+                    .iter()
+                    .all(|c1| villain.add_card(*c1).evaluate() <= hero_rank)
+            });
+            if is_nuts {
+                count += 1;
+            }
+        }
+        count
+    }
+
+    /// Returns the probability that `self` (hero's hole cards) beats `villain` on the river, where
+    /// `board` is the flop (3 cards) and the turn and river cards are dealt from the remaining
+    /// cards. Ties count as not beating villain. `self`, `villain` and `board` must not share any
+    /// cards.
+    pub fn probability_improve_by_river(&self, villain: Hand, board: Hand) -> f64 {
+        assert_eq!(board.len(), 3);
+        assert_eq!(self.len(), 2);
+        assert_eq!(villain.len(), 2);
+        let hero = *self + board;
+        let villain = villain + board;
+        assert_eq!(hero.mask & villain.mask, board.mask);
+        let remaining = Cards {
+            mask: VALID_MASK & !(hero.mask | villain.mask),
+        }
+        .collect::<Vec<_>>();
+        let len = remaining.len();
+        let (mut wins, mut total) = (0, 0);
+        for i in 0..(len - 1) {
+            let hero = hero.add_card(remaining[i]);
+            let villain = villain.add_card(remaining[i]);
+            for j in (i + 1)..len {
+                if hero.add_card(remaining[j]).evaluate()
+                    > villain.add_card(remaining[j]).evaluate()
+                {
+                    wins += 1;
+                }
+                total += 1;
+            }
+        }
+        wins as f64 / total as f64
+    }
+
+    /// Returns hero's and villain's hands combined with `board`, and the remaining cards to check
+    /// (no cards when hero is already winning).
+    fn out_candidates(&self, villain: Hand, board: Hand) -> (Hand, Hand, Cards) {
+        assert!(3 <= board.len() && board.len() <= 4);
+        assert_eq!(self.len(), 2);
+        assert_eq!(villain.len(), 2);
+        let hero = *self + board;
+        let villain = villain + board;
+        assert_eq!(hero.mask & villain.mask, board.mask);
+        let mask = if hero.evaluate() > villain.evaluate() {
+            0
+        } else {
+            VALID_MASK & !(hero.mask | villain.mask)
+        };
+        (hero, villain, Cards { mask })
+    }
+
+    /// Computes the strength of 1-4 card hand without the lookup tables.
+    fn evaluate_small(&self) -> u16 {
+        let mut count = [0; NUMBER_OF_RANKS];
+        for card in self.cards() {
+            count[card.rank() as usize] += 1;
+        }
+        evaluate_rank_count(&count)
+    }
+
+    /// Returns the strength of the best Razz (ace-to-five lowball) hand of 5-7 card hand `self`.
+    /// Unlike `evaluate()`, lower values are stronger: aces are low, straights and flushes do not
+    /// count, and the nut low A-2-3-4-5 is 0. Unpaired hands are always stronger than paired
+    /// hands, and `get_hand_category()` of the return value gives the pairing category
+    /// (`HighCard`, `OnePair`, `TwoPair`, `ThreeOfAKind`, `FullHouse` or `FourOfAKind`).
+    /// This function panics when `self.len() < 5 || self.len() > 7`.
+    pub fn evaluate_razz(&self) -> u16 {
+        assert!(5 <= self.len() && self.len() <= 7);
+        // with aces low, the weakest high hand is the strongest low hand
+        HandCombinations::new(*self, 5)
+            .map(|five| {
+                let mut count = [0; NUMBER_OF_RANKS];
+                for card in five.cards() {
+                    count[(card.rank() as usize + 1) % NUMBER_OF_RANKS] += 1;
+                }
+                evaluate_rank_count(&count)
+            })
+            .min()
+            .unwrap()
+    }
+
+    /// Returns the strength of the best ace-to-five lowball hand of 5-7 card hand `self`, where
+    /// lower values are stronger. This is the same ranking as `evaluate_razz()`: the wheel
+    /// A-2-3-4-5 is the nuts regardless of suits.
+    /// This function panics when `self.len() < 5 || self.len() > 7`.
+    #[inline]
+    pub fn evaluate_lowball_a5(&self) -> u16 {
+        self.evaluate_razz()
+    }
+
+    /// Returns the strength of 4-card Badugi hand `self`. Lower values are stronger.
+    /// The hand plays its largest subset of cards with distinct ranks and distinct suits, so any
+    /// 4-card badugi beats any 3-card hand, and so on. Aces are low, and hands of the same size are
+    /// compared from the highest card: A-2-3-4 of four suits is 0, the strongest, and a single king
+    /// (e.g., K-K-K-K) is the weakest.
+    pub fn evaluate_badugi(&self) -> u16 {
+        assert_eq!(self.len(), 4);
+        let cards = self.to_card_array();
+        let mut best = u16::MAX;
+        for subset in 1..16_usize {
+            let (mut ranks, mut suits) = (0_u16, 0_u8);
+            let mut is_badugi = true;
+            for (i, card) in cards.iter().enumerate() {
+                if (subset >> i) & 1 != 0 {
+                    let rank_bit = 1 << ((card.rank() as usize + 1) % NUMBER_OF_RANKS);
+                    let suit_bit = 1 << card.suit();
+                    is_badugi &= (ranks & rank_bit) == 0 && (suits & suit_bit) == 0;
+                    ranks |= rank_bit;
+                    suits |= suit_bit;
+                }
+            }
+            if is_badugi {
+                // subtract the lowest 4-card hand A-2-3-4 so that the nuts is 0
+                let size = subset.count_ones() as u16;
+                best = best.min((((4 - size) << 13) | ranks) - 0b1111);
+            }
+        }
+        best
+    }
+}
+
+/// index of A-6-4-3-2 within the high card and flush categories
+const ACE_SIX_INDEX: u16 = 784;
+
+impl Hand {
+    /// Returns the strength of the best 2-7 lowball (deuce-to-seven) hand of 5-7 card hand `self`.
+    /// Lower values are stronger: aces are always high, and straights and flushes count against
+    /// the hand, so 7-5-4-3-2 of mixed suits is the strongest hand. The return value is the same
+    /// as `evaluate()` except that A-5-4-3-2 is an ace-high hand rather than a straight, so
+    /// `get_hand_category()` still gives the usual category.
+    /// This function panics when `self.len() < 5 || self.len() > 7`.
+    pub fn evaluate_27_triple_draw(&self) -> u16 {
+        assert!(5 <= self.len() && self.len() <= 7);
+        HandCombinations::new(*self, 5)
+            .map(|five| five.evaluate_27_five())
+            .min()
+            .unwrap()
+    }
+
+    /// Computes the 2-7 lowball strength of 5-card hand (see `evaluate_27_triple_draw()`).
+    fn evaluate_27_five(&self) -> u16 {
+        let rank = self.evaluate();
+        let index = rank & 0x0fff;
+        match get_hand_category(rank) {
+            // A-5-4-3-2 is an ace-high hand placed just below A-6-4-3-2
+            HandCategory::Straight if index == 0 => ACE_SIX_INDEX,
+            HandCategory::StraightFlush if index == 0 => {
+                ((HandCategory::Flush as u16) << 12) | ACE_SIX_INDEX
+            }
+            HandCategory::HighCard | HandCategory::Flush if index >= ACE_SIX_INDEX => rank + 1,
+            _ => rank,
+        }
+    }
+}
+
+impl Hand {
+    /// Splits 7-card hand `self` into a 5-card high hand and a 2-card low hand for Pai Gow Poker
+    /// by the house way, and returns them as `(high, low)`:
+    ///
+    /// - no pair: the highest card stays high, and the second and third highest cards go low
+    /// - one pair: the pair stays high, and the two highest singletons go low
+    /// - two pair: the pairs are split, unless the higher pair is tens or lower and there is an
+    ///   ace singleton, in which case both pairs stay high and the ace and the highest other
+    ///   singleton go low
+    /// - three pair: the highest pair goes low
+    /// - three of a kind: the trips stay high and the two highest singletons go low, except that
+    ///   trip aces are split into a pair of aces high and an ace with the highest singleton low
+    /// - two three of a kinds: a pair of the higher trips goes low
+    /// - full house: the trips stay high and the highest pair goes low
+    /// - four of a kind: the quads stay high with the best low hand of the remaining cards
+    ///
+    /// A straight or flush (when the hand has at most one pair and no trips) is played high, with
+    /// the best low hand that keeps it.
+    /// Returns `None` when `self` does not consist of 7 cards.
+    pub fn evaluate_pai_gow_high_and_low(&self) -> Option<(Self, Self)> {
+        if self.len() != 7 {
+            return None;
+        }
+        let low = self.pai_gow_house_way_low();
+        let high = *self - low;
+        debug_assert!(high.outranks_pai_gow_low(&low));
+        Some((high, low))
+    }
+
+    /// Chooses the low hand of 7-card hand `self` by the house way (see
+    /// `evaluate_pai_gow_high_and_low()`).
+    fn pai_gow_house_way_low(&self) -> Self {
+        // ranks of each multiplicity in descending order
+        let ranks_of = |n: u8| {
+            (0..NUMBER_OF_RANKS as u8)
+                .rev()
+                .filter(|rank| self.count_rank(*rank) == n)
+                .collect::<Vec<_>>()
+        };
+        let (quads, trips, pairs, singles) = (ranks_of(4), ranks_of(3), ranks_of(2), ranks_of(1));
+        let cards_of = |rank: u8, n: usize| {
+            Self::from_slice(
+                &self
+                    .cards()
+                    .filter(|card| card.rank() == rank)
+                    .take(n)
+                    .collect::<Vec<_>>(),
+            )
+        };
+
+        if quads.is_empty() && trips.is_empty() && pairs.len() <= 1 {
+            if let Some(low) = self.pai_gow_best_low(|high| {
+                get_hand_category(high.evaluate()) >= HandCategory::Straight
+            }) {
+                return low;
+            }
+        }
+
+        if !quads.is_empty() {
+            let quads = cards_of(quads[0], 4);
+            self.pai_gow_best_low(|high| high.mask & quads.mask == quads.mask)
+                .unwrap()
+        } else if trips.len() == 2 {
+            cards_of(trips[0], 2)
+        } else if trips.len() == 1 && !pairs.is_empty() {
+            cards_of(pairs[0], 2)
+        } else if trips.len() == 1 && trips[0] == 12 {
+            cards_of(12, 1) + cards_of(singles[0], 1)
+        } else if trips.len() == 1 {
+            cards_of(singles[0], 1) + cards_of(singles[1], 1)
+        } else if pairs.len() == 3 {
+            cards_of(pairs[0], 2)
+        } else if pairs.len() == 2 && pairs[0] <= 8 && singles[0] == 12 {
+            cards_of(12, 1) + cards_of(singles[1], 1)
+        } else if pairs.len() == 2 {
+            cards_of(pairs[1], 2)
+        } else if pairs.len() == 1 {
+            cards_of(singles[0], 1) + cards_of(singles[1], 1)
+        } else {
+            cards_of(singles[1], 1) + cards_of(singles[2], 1)
+        }
+    }
+
+    /// Returns the strongest low hand of 7-card hand `self` among the splits whose high hand
+    /// satisfies `is_allowed`, or `None` if there is no such split.
+    fn pai_gow_best_low(&self, is_allowed: impl Fn(&Self) -> bool) -> Option<Self> {
+        let cards = self.cards().collect::<Vec<_>>();
+        let mut best: Option<(u16, Self)> = None;
+        for i in 0..6 {
+            for j in (i + 1)..7 {
+                let low = Self::from_slice(&[cards[i], cards[j]]);
+                let high = *self - low;
+                if !is_allowed(&high) {
+                    continue;
+                }
+                let rank = low.evaluate_n(2);
+                match best {
+                    Some((best_rank, _)) if best_rank >= rank => {}
+                    _ => best = Some((rank, low)),
+                }
+            }
+        }
+        best.map(|(_, low)| low)
+    }
+
+    /// Returns whether 5-card hand `self` outranks 2-card hand `low`. Because the 5-card hand has
+    /// more cards, it wins when the first two cards compare equal.
+    fn outranks_pai_gow_low(&self, low: &Self) -> bool {
+        // cards are in ascending order of rank
+        let low_ranks = low.cards().map(|card| card.rank()).collect::<Vec<_>>();
+        let (r0, r1) = (low_ranks[1], low_ranks[0]);
+        let is_low_pair = r0 == r1;
+        match get_hand_category(self.evaluate()) {
+            HandCategory::HighCard => {
+                let top = self.high_card_rank();
+                let second = (0..top)
+                    .rev()
+                    .find(|rank| self.contains_rank(*rank))
+                    .unwrap();
+                !is_low_pair && (top, second) >= (r0, r1)
+            }
+            HandCategory::OnePair => {
+                let pair = (0..13).find(|rank| self.count_rank(*rank) == 2).unwrap();
+                !is_low_pair || pair >= r0
+            }
+            _ => true,
+        }
+    }
+}
+
+/// Returns the rank set of the cards in `mask` (in the same layout as `Hand::get_mask()`).
+#[inline]
+fn mask_rankset(mask: u64) -> u64 {
+    (mask | (mask >> 16) | (mask >> 32) | (mask >> 48)) & 0x1fff
+}
+
+/// Returns `rankset` shifted up by one with the ace appended below the deuce, so that bit 0 is the
+/// ace, bit 1 is the deuce and bit 13 is the ace again. This covers A-2-3-4-5 in straight checks
+/// and the ace in A-8 lows.
+#[inline]
+pub(crate) fn with_low_ace(rankset: u64) -> u64 {
+    (rankset << 1) | (rankset >> 12)
+}
+
+/// Computes the strength of 1-5 cards from the number of cards of each rank, ignoring straights
+/// and flushes. The upper 4 bits hold the hand category; the lower 12 bits are the index of the
+/// primary ranks and kicker ranks in the combinatorial number system.
+fn evaluate_rank_count(count: &[usize; NUMBER_OF_RANKS]) -> u16 {
+    // ranks grouped by their counts, in descending order
+    let mut groups = vec![Vec::new(); 5];
+    for rank in (0..NUMBER_OF_RANKS).rev() {
+        groups[count[rank]].push(rank);
+    }
+
+    let (category, primary, kickers): (_, &[usize], _) = if !groups[4].is_empty() {
+        (HandCategory::FourOfAKind, &groups[4], &groups[1])
+    } else if !groups[3].is_empty() && !groups[2].is_empty() {
+        (HandCategory::FullHouse, &groups[3], &groups[2])
+    } else if !groups[3].is_empty() {
+        (HandCategory::ThreeOfAKind, &groups[3], &groups[1])
+    } else if groups[2].len() >= 2 {
+        (HandCategory::TwoPair, &groups[2], &groups[1])
+    } else if !groups[2].is_empty() {
+        (HandCategory::OnePair, &groups[2], &groups[1])
+    } else {
+        (HandCategory::HighCard, &[], &groups[1])
+    };
+
+    let kicker_combinations = count_combinations(NUMBER_OF_RANKS, kickers.len());
+    let index =
+        combination_index(primary) as u64 * kicker_combinations + combination_index(kickers) as u64;
+    debug_assert!(index < 1 << 12);
+    ((category as u16) << 12) | index as u16
+}
+
+/// Returns the strength of the 5-card hand consisting of `c0`-`c4`, without building a `Hand`.
+/// The return value is the same as `Hand::from_slice(&[c0, c1, c2, c3, c4]).evaluate()`.
+/// The cards must be distinct.
+#[inline]
+pub fn evaluate_five(c0: Card, c1: Card, c2: Card, c3: Card, c4: Card) -> u16 {
+    let (k0, m0) = unsafe { *CARDS.get_unchecked(c0.index()) };
+    let (k1, m1) = unsafe { *CARDS.get_unchecked(c1.index()) };
+    let (k2, m2) = unsafe { *CARDS.get_unchecked(c2.index()) };
+    let (k3, m3) = unsafe { *CARDS.get_unchecked(c3.index()) };
+    let (k4, m4) = unsafe { *CARDS.get_unchecked(c4.index()) };
+    let key = (0x3333 << SUIT_SHIFT) + k0 + k1 + k2 + k3 + k4;
+    evaluate_key_mask(key, m0 | m1 | m2 | m3 | m4)
+}
+
+/// Looks up the hand strength from `Hand::key` and `Hand::mask`.
+#[inline]
+fn evaluate_key_mask(key: u64, mask: u64) -> u16 {
+    let is_flush = key & FLUSH_MASK;
+    if is_flush > 0 {
+        let flush_key = (mask >> (4 * is_flush.leading_zeros())) as u16;
+        unsafe { *LOOKUP_FLUSH.get_unchecked(flush_key as usize) }
+    } else {
+        let rank_key = key as u32 as usize;
+        let offset = unsafe { *OFFSETS.get_unchecked(rank_key >> OFFSET_SHIFT) as usize };
+        let hash_key = rank_key.wrapping_add(offset);
+        unsafe { *LOOKUP.get_unchecked(hash_key) }
+    }
+}
+
+/// Computes the index of the combination `ranks` (sorted in descending order) in the
+/// combinatorial number system, which preserves the lexicographic order.
+pub(crate) fn combination_index(ranks: &[usize]) -> u16 {
+    let k = ranks.len();
+    let index = ranks
+        .iter()
+        .enumerate()
+        .map(|(i, rank)| count_combinations(*rank, k - i))
+        .sum::<u64>();
+    debug_assert!(index < 1 << 12);
+    index as u16
+}
+
+/// Iterator over the cards in a hand, created by `Hand::cards()`.
+#[derive(Clone, Debug)]
+pub struct Cards {
+    mask: u64,
+}
+
+impl Iterator for Cards {
+    type Item = Card;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.mask == 0 {
+            return None;
+        }
+        let m = self.mask;
+        let rank = mask_rankset(m).trailing_zeros() as usize;
+        for i in (4 * rank)..(4 * rank + 4) {
+            if (m & CARDS[i].1) != 0 {
+                self.mask ^= CARDS[i].1;
+                return Card::try_from(i).ok();
+            }
+        }
+        unreachable!()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.mask.count_ones() as usize;
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for Cards {}
+
+/// Fixed-capacity array of the cards in a hand, created by `Hand::to_card_array()`.
+/// Dereferences to `[Card]` of length `Hand::len()`.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct CardArray {
+    cards: [Card; 7],
+    len: usize,
+}
+
+impl CardArray {
+    /// Returns the cards as a slice.
+    #[inline]
+    pub fn as_slice(&self) -> &[Card] {
+        &self.cards[..self.len]
+    }
+}
+
+impl Deref for CardArray {
+    type Target = [Card];
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.as_slice()
+    }
+}
+
+impl Add for Hand {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self::Output {
+        Self {
+            key: self
+                .key
+                .wrapping_add(rhs.key)
+                .wrapping_sub(0x3333 << SUIT_SHIFT),
+            mask: self.mask.wrapping_add(rhs.mask),
+        }
+    }
+}
+
+impl AddAssign for Hand {
+    fn add_assign(&mut self, rhs: Self) {
+        self.key = self.key.wrapping_add(rhs.key);
+        self.key = self.key.wrapping_sub(0x3333 << SUIT_SHIFT);
+        self.mask = self.mask.wrapping_add(rhs.mask);
+    }
+}
+
+impl Sub for Hand {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self {
+            key: self
+                .key
+                .wrapping_sub(rhs.key)
+                .wrapping_add(0x3333 << SUIT_SHIFT),
+            mask: self.mask.wrapping_sub(rhs.mask),
+        }
+    }
+}
+
+impl SubAssign for Hand {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.key = self.key.wrapping_sub(rhs.key);
+        self.key = self.key.wrapping_add(0x3333 << SUIT_SHIFT);
+        self.mask = self.mask.wrapping_sub(rhs.mask);
+    }
+}
+
+/// Compares hand strengths computed by `evaluate()`, so that a stronger hand is greater.
+/// To keep consistent with `==`, which compares the cards, this returns `None` for different hands
+/// of the same strength (use `beats()` to detect ties) and for hands of fewer than 5 or more than 7
+/// cards. Like `beats()`, this does not check `is_valid()`, and the result is meaningless for
+/// invalid hands.
+impl PartialOrd for Hand {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        if self == other {
+            return Some(Ordering::Equal);
+        }
+        match (self.evaluate_safe(), other.evaluate_safe()) {
+            (Some(lhs), Some(rhs)) if lhs != rhs => Some(lhs.cmp(&rhs)),
+            _ => None,
+        }
+    }
+}
+
+impl Default for Hand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Formats the hand as space-separated cards in ascending order of card index (e.g., "2s Kd Ah").
+/// The output can be parsed back by `FromStr`.
+impl fmt::Display for Hand {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, card) in self.cards().enumerate() {
+            if i > 0 {
+                write!(f, " ")?;
+            }
+            let rank = rank_to_string(card.rank());
+            write!(f, "{}{}", rank, suit_to_string(card.suit()))?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Hand {
+    type Err = String;
+
+    fn from_str(hand_str: &str) -> Result<Self, Self::Err> {
+        let mut hand = Self::new();
+        let mut chars = hand_str.char_indices();
+        loop {
+            let start = match chars.find(|(_, ch)| !ch.is_whitespace()) {
+                Some((i, _)) => i,
+                None => return Ok(hand),
+            };
+            let end = chars
+                .next()
+                .map_or(hand_str.len(), |(i, ch)| i + ch.len_utf8());
+            hand = hand.add_card(hand_str[start..end].parse::<Card>()?);
+        }
+    }
+}
+
+/// Treats `bytes` as an array of card indices (one byte per card). Fails when `bytes` has more than
+/// 7 cards, or contains an invalid card index or a duplicate card.
+impl TryFrom<&[u8]> for Hand {
+    type Error = HandError;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        if bytes.len() > 7 {
+            return Err(HandError::TooManyCards(bytes.len()));
+        }
+        bytes
+            .iter()
+            .try_fold(Self::new(), |hand, byte| hand.try_add_card(*byte as usize))
+    }
+}
+
+/// Encodes `hand` as an array of card indices in ascending order (one byte per card).
+impl From<Hand> for Vec<u8> {
+    fn from(hand: Hand) -> Self {
+        hand.cards().map(|card| card.index() as u8).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::*;
+    use std::collections::HashSet;
+
+    fn card(index: usize) -> Card {
+        Card::try_from(index).unwrap()
+    }
+
+    fn evaluate_hand_str(hand_str: &str) -> u16 {
+        let hand = hand_str.parse::<Hand>().unwrap();
+        assert_eq!(hand.len(), 7);
+        hand.evaluate()
+    }
+
+    #[test]
+    fn test_hand_category_conversion() {
+        for value in 0..=8 {
+            let category = HandCategory::try_from(value).unwrap();
+            assert_eq!(u8::from(category), value);
+            assert_eq!(category, get_hand_category((value as u16) << 12));
+        }
+        assert_eq!(HandCategory::try_from(0), Ok(HandCategory::HighCard));
+        assert_eq!(HandCategory::try_from(8), Ok(HandCategory::StraightFlush));
+        assert_eq!(HandCategory::try_from(9), Err(()));
+        assert_eq!(HandCategory::try_from(255), Err(()));
+    }
+
+    #[test]
+    fn test_parser() {
+        let cards = [0, 1, 4, 7, 8, 15, 19].map(card);
+        let hand_from_vec = Hand::from_slice(&cards);
+        let hand_from_str = "2h2s3d3s4s5d6d".parse::<Hand>();
+        assert_eq!(hand_from_str, Ok(hand_from_vec));
+        assert_eq!("".parse::<Hand>(), Ok(Hand::new()));
+        assert_eq!(
+            "A".parse::<Hand>(),
+            Err("parse failed: expected suit character, but got EOF".into())
+        );
+        assert_eq!(
+            "Ax".parse::<Hand>(),
+            Err("parse failed: expected suit character, but got 'x'".into())
+        );
+        assert_eq!(
+            "10s".parse::<Hand>(),
+            Err("parse failed: expected rank character, but got '1'".into())
+        );
+    }
+
+    #[test]
+    fn test_cards() {
+        assert_eq!(Hand::new().cards().next(), None);
+        let indices = [51, 0, 17, 3, 48, 26, 12];
+        for len in 0..=indices.len() {
+            let hand =
+                Hand::from_slice(&indices[..len].iter().map(|i| card(*i)).collect::<Vec<_>>());
+            let mut expected = indices[..len].to_vec();
+            expected.sort_unstable();
+            let cards = hand.cards();
+            assert_eq!(cards.len(), len);
+            assert_eq!(cards.map(usize::from).collect::<Vec<_>>(), expected);
+        }
+    }
+
+    #[test]
+    fn test_is_blocked_by() {
+        let hand = |hand_str: &str| hand_str.parse::<Hand>().unwrap();
+        assert!(hand("AcKc").is_blocked_by(hand("Ac")));
+        assert!(hand("AcKc").is_blocked_by(hand("Kc2d")));
+        assert!(hand("AcKc").is_blocked_by(hand("AcKc")));
+        assert!(!hand("AcKc").is_blocked_by(hand("AsKd")));
+        assert!(!hand("AcKc").is_blocked_by(Hand::new()));
+        assert!(!Hand::new().is_blocked_by(hand("AcKc")));
+        assert!(hand("3h8c").is_blocked_by(hand("2s3h4c5d6s7h8c")));
+        assert!(!hand("2s3h").is_blocked_by(hand("2h3s4c5d6s7h8c")));
+    }
+
+    #[test]
+    fn test_shared_cards() {
+        let hand = |hand_str: &str| hand_str.parse::<Hand>().unwrap();
+        let hand_a = hand("AsKd");
+        let hand_b = hand("AsQh");
+        let board = hand("2c7h9dTsJs");
+        assert_eq!((hand_a + board).shared_cards(&board), board);
+        assert_eq!(board.shared_cards(&(hand_a + board)), board);
+        assert_eq!(
+            (hand_a + board).shared_cards(&(hand_b + board)),
+            hand("As") + board
+        );
+        assert_eq!(hand_a.shared_cards(&board), Hand::new());
+        assert_eq!(hand_a.shared_cards(&Hand::new()), Hand::new());
+        assert!((hand_a + board).shared_cards(&(hand_b + board)).is_valid());
+    }
+
+    #[test]
+    fn test_rank_queries() {
+        let hand = "AsAhKc".parse::<Hand>().unwrap();
+        assert!(hand.contains_rank(12));
+        assert!(hand.contains_rank(11));
+        assert!(!hand.contains_rank(0));
+        assert_eq!(hand.count_rank(12), 2);
+        assert_eq!(hand.count_rank(11), 1);
+        assert_eq!(hand.count_rank(0), 0);
+
+        let hand = "2s2h2c2d3s".parse::<Hand>().unwrap();
+        assert_eq!(hand.count_rank(0), 4);
+        assert_eq!(hand.count_rank(1), 1);
+        assert!((2..13).all(|rank| !hand.contains_rank(rank)));
+        assert!((0..13).all(|rank| !Hand::new().contains_rank(rank)));
+
+        let full_house = "KsKhKc7d7s".parse::<Hand>().unwrap();
+        let distribution = full_house.rank_distribution();
+        assert_eq!(distribution, [0, 0, 0, 0, 0, 2, 0, 0, 0, 0, 0, 3, 0]);
+        assert_eq!(distribution.iter().filter(|count| **count >= 2).count(), 2);
+        assert_eq!(distribution.iter().sum::<u8>() as usize, full_house.len());
+        assert_eq!(
+            "AsKsQsJsTs".parse::<Hand>().unwrap().rank_distribution()[8..],
+            [1; 5]
+        );
+        assert_eq!(Hand::new().rank_distribution(), [0; 13]);
+    }
+
+    #[test]
+    fn test_high_card_rank() {
+        let high = |hand_str: &str| hand_str.parse::<Hand>().unwrap().high_card_rank();
+        assert_eq!(high("AsKd2c"), 12);
+        assert_eq!(high("9s8h7d"), 7);
+        assert_eq!(high("2c"), 0);
+        assert_eq!(high("2s2h2c2d3d"), 1);
+    }
+
+    #[test]
+    fn test_low_card_rank() {
+        let low = |hand_str: &str| hand_str.parse::<Hand>().unwrap().low_card_rank();
+        assert_eq!(low("As2c3d"), 0);
+        assert_eq!(low("AsKd"), 11);
+        assert_eq!(low("Ad"), 12);
+        assert_eq!(low("9s8h7d7c"), 5);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_low_card_rank_empty() {
+        Hand::new().low_card_rank();
+    }
+
+    #[test]
+    fn test_missing_cards_to_flush() {
+        let missing = |hand_str: &str| hand_str.parse::<Hand>().unwrap().missing_cards_to_flush();
+        assert_eq!(missing(""), Some(5));
+        assert_eq!(missing("AsKh"), Some(4));
+        assert_eq!(missing("AsKs7d"), Some(3));
+        assert_eq!(missing("AsKs7s2d"), Some(2));
+        assert_eq!(missing("AsKs7s2s3d"), Some(1));
+        assert_eq!(missing("2c3c4c5h6h7hKd"), Some(2));
+        assert_eq!(missing("2c3c4c5c6h7hKc"), None);
+        assert_eq!(missing("AdKdQdJdTd"), None);
+    }
+
+    #[test]
+    fn test_missing_cards_to_straight() {
+        let missing = |hand_str: &str| {
+            hand_str
+                .parse::<Hand>()
+                .unwrap()
+                .missing_cards_to_straight()
+        };
+        assert_eq!(missing(""), Some(5));
+        assert_eq!(missing("2s"), Some(4));
+        assert_eq!(missing("2s8h"), Some(4));
+        // open-ended and inside draws
+        assert_eq!(missing("8s9hTcJd2s"), Some(1));
+        assert_eq!(missing("8s9hJcQd2s"), Some(1));
+        assert_eq!(missing("As2h3c4d9s"), Some(1));
+        assert_eq!(missing("AsKhQcJd2s"), Some(1));
+        assert_eq!(missing("AsKhQc2d3s"), Some(2));
+        // made straights, including the wheel
+        assert_eq!(missing("As2h3c4d5s"), None);
+        assert_eq!(missing("TsJhQcKdAs"), None);
+        assert_eq!(missing("2s2h3c4d5s6hKc"), None);
+        assert_eq!(missing("KsAh2c3d4s"), Some(1));
+    }
+
+    #[test]
+    fn test_category_predicates() {
+        let predicates = |hand: Hand| {
+            [
+                hand.has_pair(),
+                hand.has_two_pair(),
+                hand.has_trips(),
+                hand.has_quads(),
+                hand.has_straight(),
+                hand.has_flush(),
+            ]
+        };
+
+        // in 5-card hands, each predicate corresponds to a set of hand categories
+        for hand in AllHandsIterator::new(5) {
+            let category = get_hand_category(hand.evaluate());
+            let expected = match category {
+                HandCategory::HighCard => [false, false, false, false, false, false],
+                HandCategory::OnePair => [true, false, false, false, false, false],
+                HandCategory::TwoPair => [true, true, false, false, false, false],
+                HandCategory::ThreeOfAKind => [true, false, true, false, false, false],
+                HandCategory::Straight => [false, false, false, false, true, false],
+                HandCategory::Flush => [false, false, false, false, false, true],
+                HandCategory::FullHouse => [true, true, true, false, false, false],
+                HandCategory::FourOfAKind => [true, false, true, true, false, false],
+                HandCategory::StraightFlush => [false, false, false, false, true, true],
+            };
+            assert_eq!(predicates(hand), expected);
+        }
+
+        // 7-card hands of `test_edge_cases()`
+        let hand = |hand_str: &str| hand_str.parse::<Hand>().unwrap();
+        assert_eq!(
+            predicates(hand("AdQsJc5d4d3d2d")),
+            [false, false, false, false, true, true]
+        );
+        assert_eq!(
+            predicates(hand("3d3h3s2c2d2h2s")),
+            [true, true, true, true, false, false]
+        );
+        assert_eq!(
+            predicates(hand("5h4c3s3c2d2c2h")),
+            [true, true, true, false, false, false]
+        );
+        assert_eq!(
+            predicates(hand("AhKhQhJh9h9c9s")),
+            [true, false, true, false, false, true]
+        );
+        assert_eq!(
+            predicates(hand("AhKcKdKhQcJdTs")),
+            [true, false, true, false, true, false]
+        );
+        assert_eq!(
+            predicates(hand("Ac8c7c5d4d3d2d")),
+            [false, false, false, false, true, false]
+        );
+        assert_eq!(
+            predicates(hand("AsAhKsKhQsQhJs")),
+            [true, true, false, false, false, false]
+        );
+        assert_eq!(
+            predicates(hand("8s7s5h4c3c2d2c")),
+            [true, false, false, false, false, false]
+        );
+        assert_eq!(
+            predicates(hand("9h8s7d5d4d3c2d")),
+            [false, false, false, false, false, false]
+        );
+        assert_eq!(predicates(Hand::new()), [false; 6]);
+    }
+
+    #[test]
+    fn test_from_internal_mask() {
+        assert_eq!(Hand::from_internal_mask(0), Ok(Hand::new()));
+        for i in 0..(NUMBER_OF_CARDS - 4) {
+            let hand = Hand::new().add_card(card(i));
+            for j in (i + 1)..(NUMBER_OF_CARDS - 3) {
+                let hand = hand.add_card(card(j));
+                for k in (j + 1)..(NUMBER_OF_CARDS - 2) {
+                    let hand = hand.add_card(card(k));
+                    for m in (k + 1)..(NUMBER_OF_CARDS - 1) {
+                        let hand = hand.add_card(card(m));
+                        for n in (m + 1)..NUMBER_OF_CARDS {
+                            let hand = hand.add_card(card(n));
+                            assert_eq!(Hand::from_internal_mask(hand.get_mask()), Ok(hand));
+                        }
+                    }
+                }
+            }
+        }
+
+        let hand = "AsKsQsJsTs9s8s".parse::<Hand>().unwrap();
+        assert_eq!(Hand::from_internal_mask(hand.get_mask()), Ok(hand));
+        assert_eq!(
+            Hand::from_internal_mask(hand.get_mask() | 1),
+            Err(HandError::TooManyCards(8))
+        );
+        assert_eq!(
+            Hand::from_internal_mask(1 << 13),
+            Err(HandError::InvalidMask(1 << 13))
+        );
+        assert_eq!(
+            Hand::from_internal_mask(1 << 63),
+            Err(HandError::InvalidMask(1 << 63))
+        );
+    }
+
+    #[test]
+    fn test_from_u64_mask() {
+        assert_eq!(Hand::from_u64_mask(0), Ok(Hand::new()));
+        let hand = Hand::from_u64_mask((1 << 0) | (1 << 47) | (1 << 51)).unwrap();
+        assert_eq!(hand, "2sKdAd".parse().unwrap());
+        let hand = Hand::from_u64_mask(0x7f << 45).unwrap();
+        assert_eq!(hand, "KhKcKdAsAhAcAd".parse().unwrap());
+        assert_eq!(
+            Hand::from_u64_mask(1 << 52),
+            Err(HandError::InvalidCardIndex(52))
+        );
+        assert_eq!(
+            Hand::from_u64_mask((1 << 53) - 1),
+            Err(HandError::InvalidCardIndex(52))
+        );
+        assert_eq!(Hand::from_u64_mask(0xff), Err(HandError::TooManyCards(8)));
+        assert_eq!(
+            HandError::InvalidCardIndex(52).to_string(),
+            "invalid card index: 52 (must be 0-51)"
+        );
+    }
+
+    #[test]
+    fn test_try_add_card() {
+        let mut count = 0;
+        for i in 0..(NUMBER_OF_CARDS - 4) {
+            let hand = Hand::new().try_add_card(i).unwrap();
+            for j in (i + 1)..(NUMBER_OF_CARDS - 3) {
+                let hand = hand.try_add_card(j).unwrap();
+                for k in (j + 1)..(NUMBER_OF_CARDS - 2) {
+                    let hand = hand.try_add_card(k).unwrap();
+                    for m in (k + 1)..(NUMBER_OF_CARDS - 1) {
+                        let hand = hand.try_add_card(m).unwrap();
+                        for n in (m + 1)..NUMBER_OF_CARDS {
+                            let expected = hand.add_card(card(n));
+                            assert_eq!(hand.try_add_card(n), Ok(expected));
+                            count += 1;
+                        }
+                    }
+                }
+            }
+        }
+        assert_eq!(count, 2598960);
+
+        let hand = "AsKsQsJsTs9s8s".parse::<Hand>().unwrap();
+        assert_eq!(
+            Hand::new().try_add_card(52),
+            Err(HandError::InvalidCardIndex(52))
+        );
+        assert_eq!(
+            hand.try_add_card(48),
+            Err(HandError::DuplicateCard(card(48)))
+        );
+        assert_eq!(hand.try_add_card(0), Err(HandError::TooManyCards(8)));
+    }
+
+    #[test]
+    fn test_try_from_bytes() {
+        assert_eq!(
+            Hand::try_from(&[0u8, 1, 2, 3, 4][..]),
+            Ok(Hand::from_slice(&[0, 1, 2, 3, 4].map(card)))
+        );
+        assert_eq!(
+            Hand::try_from(&[51u8, 0, 25][..]),
+            Ok(Hand::from_slice(&[0, 25, 51].map(card)))
+        );
+        assert_eq!(Hand::try_from(&[][..]), Ok(Hand::new()));
+        assert_eq!(
+            Hand::try_from(&[0u8, 52][..]),
+            Err(HandError::InvalidCardIndex(52))
+        );
+        assert_eq!(
+            Hand::try_from(&[7u8, 3, 7][..]),
+            Err(HandError::DuplicateCard(card(7)))
+        );
+        assert_eq!(
+            Hand::try_from(&[0u8, 1, 2, 3, 4, 5, 6, 7][..]),
+            Err(HandError::TooManyCards(8))
+        );
+    }
+
+    #[test]
+    fn test_bytes_round_trip() {
+        let hand = "AsKd2c".parse::<Hand>().unwrap();
+        assert_eq!(Vec::<u8>::from(hand), vec![2, 47, 48]);
+        assert_eq!(Vec::<u8>::from(Hand::new()), Vec::<u8>::new());
+
+        for n in 0..=5 {
+            for hand in AllHandsIterator::new(n) {
+                let bytes = Vec::<u8>::from(hand);
+                assert_eq!(bytes.len(), n);
+                assert_eq!(Hand::try_from(&bytes[..]), Ok(hand));
+            }
+        }
+        for hand_str in &["AsKsQsJsTs7d5s", "3d3h3s2c2d2h2s", "AdKdQdJd9s3h"] {
+            let hand = hand_str.parse::<Hand>().unwrap();
+            assert_eq!(Hand::try_from(&Vec::<u8>::from(hand)[..]), Ok(hand));
+        }
+    }
+
+    #[test]
+    fn test_try_remove_card() {
+        let hand = "AsKsQsJsTs9s8s".parse::<Hand>().unwrap();
+        for card in hand.cards() {
+            let removed = hand.try_remove_card(card.index()).unwrap();
+            assert_eq!(removed, hand.remove_card(card));
+            assert!(removed.is_valid());
+            assert_eq!(removed.try_add_card(card.index()), Ok(hand));
+        }
+        assert_eq!(
+            hand.try_remove_card(0),
+            Err(HandError::CardNotPresent(card(0)))
+        );
+        assert_eq!(
+            Hand::new().try_remove_card(51),
+            Err(HandError::CardNotPresent(card(51)))
+        );
+        assert_eq!(
+            hand.try_remove_card(52),
+            Err(HandError::InvalidCardIndex(52))
+        );
+    }
+
+    #[test]
+    fn test_from_fen() {
+        let hand = "AsKdQh".parse::<Hand>().unwrap();
+        assert_eq!(Hand::from_fen("As/Kd/Qh"), Ok(hand));
+        assert_eq!(Hand::from_fen("As,Kd,Qh"), Ok(hand));
+        assert_eq!(Hand::from_fen("AsKdQh"), Ok(hand));
+        assert_eq!(Hand::from_fen(" As / Kd / Qh "), Ok(hand));
+        assert_eq!(Hand::from_fen(""), Ok(Hand::new()));
+
+        assert_eq!(Hand::from_fen("As/Kd,Qh"), Err(HandError::InvalidFormat));
+        assert_eq!(Hand::from_fen("As Kd Qh"), Err(HandError::InvalidFormat));
+        assert_eq!(Hand::from_fen("As//Kd"), Err(HandError::InvalidFormat));
+        assert_eq!(Hand::from_fen("As/Kx"), Err(HandError::InvalidFormat));
+        assert_eq!(Hand::from_fen("AsK"), Err(HandError::InvalidFormat));
+        assert_eq!(Hand::from_fen("As/"), Err(HandError::InvalidFormat));
+        assert_eq!(
+            Hand::from_fen("As/Kd/As"),
+            Err(HandError::DuplicateCard(card(48)))
+        );
+        assert_eq!(
+            Hand::from_fen("2s3s4s5s6s7s8s9s"),
+            Err(HandError::TooManyCards(8))
+        );
+    }
+
+    #[test]
+    fn test_to_fen() {
+        let hand = "AsKdQh".parse::<Hand>().unwrap();
+        assert_eq!(hand.to_fen(), "Qh/Kd/As");
+        assert_eq!(Hand::new().to_fen(), "");
+        for i in (0..NUMBER_OF_CARDS).step_by(3) {
+            for j in ((i + 1)..NUMBER_OF_CARDS).step_by(5) {
+                for k in ((j + 1)..NUMBER_OF_CARDS).step_by(7) {
+                    let hand = Hand::from_slice(&[card(i), card(j), card(k)]);
+                    assert_eq!(Hand::from_fen(&hand.to_fen()), Ok(hand));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_is_valid() {
+        assert!(Hand::new().is_valid());
+        for len in 1..=7 {
+            for start in 0..NUMBER_OF_CARDS {
+                let cards = (0..len)
+                    .map(|i| card((start + 7 * i) % NUMBER_OF_CARDS))
+                    .collect::<Vec<_>>();
+                assert!(Hand::from_slice(&cards).is_valid());
+            }
+        }
+
+        let hand = "AsKsQsJsTs".parse::<Hand>().unwrap();
+        assert!(!hand.add_card(card(0)).add_card(card(0)).is_valid());
+        assert!(!hand.remove_card(card(0)).is_valid());
+        assert!(!(hand + "9s8s7s".parse::<Hand>().unwrap()).is_valid());
+        assert!(!Hand {
+            key: hand.key ^ (1 << 50),
+            mask: hand.mask,
+        }
+        .is_valid());
+        assert!(!Hand {
+            key: hand.key.wrapping_add(1),
+            mask: hand.mask,
+        }
+        .is_valid());
+        assert!(!Hand {
+            key: hand.key,
+            mask: hand.mask | (1 << 15),
+        }
+        .is_valid());
+    }
+
+    #[test]
+    fn test_best_five() {
+        for i in 0..(NUMBER_OF_CARDS - 4) {
+            let hand = Hand::new().add_card(card(i));
+            for j in (i + 1)..(NUMBER_OF_CARDS - 3) {
+                let hand = hand.add_card(card(j));
+                for k in (j + 1)..(NUMBER_OF_CARDS - 2) {
+                    let hand = hand.add_card(card(k));
+                    for m in (k + 1)..(NUMBER_OF_CARDS - 1) {
+                        let hand = hand.add_card(card(m));
+                        for n in (m + 1)..NUMBER_OF_CARDS {
+                            let hand = hand.add_card(card(n));
+                            assert_eq!(Hand::from_slice(&hand.best_five()), hand);
+                        }
+                    }
+                }
+            }
+        }
+
+        // 6-card and 7-card hands: step sizes coprime with 52 yield distinct cards
+        for len in 6..=7 {
+            for start in 0..NUMBER_OF_CARDS {
+                for step in (1..NUMBER_OF_CARDS).step_by(2).filter(|s| s % 13 != 0) {
+                    let cards = (0..len)
+                        .map(|i| card((start + step * i) % NUMBER_OF_CARDS))
+                        .collect::<Vec<_>>();
+                    let hand = Hand::from_slice(&cards);
+                    let best_five = hand.best_five();
+                    assert!(best_five.iter().all(|c| hand.contains(*c)));
+                    assert_eq!(Hand::from_slice(&best_five).evaluate(), hand.evaluate());
+                }
+            }
+        }
+
+        // random 6-card and 7-card hands, which also include flushes and straight flushes
+        let mut rng = Xorshift64::new(0x2545_f491_4f6c_dd1d);
+        let mut counter = vec![0; HandCategory::StraightFlush as usize + 1];
+        for len in 6..=7 {
+            for _ in 0..50000 {
+                let hand = rng.deal_hand(len);
+                let best_five = hand.best_five();
+                assert!(best_five.iter().all(|c| hand.contains(*c)));
+                assert_eq!(Hand::from_slice(&best_five).evaluate(), hand.evaluate());
+                counter[get_hand_category(hand.evaluate()) as usize] += 1;
+            }
+        }
+        assert!(counter[HandCategory::Flush as usize] > 0);
+        assert!(counter[HandCategory::StraightFlush as usize] > 0);
+
+        let hand = "AsAcAhAdKsQcTh".parse::<Hand>().unwrap();
+        let expected = "AsAcAhAdKs".parse::<Hand>().unwrap();
+        assert_eq!(Hand::from_slice(&hand.best_five()), expected);
+        let hand = "AsKsQsJsTs9s8s".parse::<Hand>().unwrap();
+        let expected = "AsKsQsJsTs".parse::<Hand>().unwrap();
+        assert_eq!(Hand::from_slice(&hand.best_five()), expected);
+        let hand = "Ah9h7h5h3h2hKd".parse::<Hand>().unwrap();
+        let expected = "Ah9h7h5h3h".parse::<Hand>().unwrap();
+        assert_eq!(Hand::from_slice(&hand.best_five()), expected);
+    }
+
+    #[test]
+    fn test_kicker_mask() {
+        let hand = |hand_str: &str| hand_str.parse::<Hand>().unwrap();
+        let kickers = |hand_str: &str| hand(hand_str).kicker_mask();
+
+        // one pair: the three highest non-pair cards
+        assert_eq!(kickers("AdAsKhQdJs3s2c"), hand("KhQdJs").get_mask());
+        assert_eq!(kickers("8s7s5h4c3c2d2c"), hand("8s7s5h").get_mask());
+        assert_eq!(kickers("AsAdKh9c2s"), hand("Kh9c2s").get_mask());
+
+        // other categories with kickers
+        assert_eq!(kickers("AdKdQdJd9s3h2c"), hand("KdQdJd9s").get_mask());
+        assert_eq!(kickers("AsAhKsKhQs9h2d"), hand("Qs").get_mask());
+        assert_eq!(kickers("7d5c4c3c2d2s2h"), hand("7d5c").get_mask());
+        assert_eq!(kickers("AsAcAhAdKsQcTh"), hand("Ks").get_mask());
+
+        // no kickers
+        assert_eq!(kickers("AsKsQsJsTs7d5s"), 0);
+        assert_eq!(kickers("AsAdAhKcKdKh2d"), 0);
+        assert_eq!(kickers("AhKhQhJh9h9c9s"), 0);
+        assert_eq!(kickers("AhKcKdKhQcJdTs"), 0);
+    }
+
+    #[test]
+    fn test_evaluate_n() {
+        let evaluate_n = |hand_str: &str, n: usize| hand_str.parse::<Hand>().unwrap().evaluate_n(n);
+
+        for hand_str in &["AsKsQsJsTs7d5s", "AsAcAhKhQd5c3s", "9h8s7d5d4d3c2d"] {
+            let hand = hand_str.parse::<Hand>().unwrap();
+            assert_eq!(hand.evaluate_n(7), hand.evaluate());
+            assert_eq!(hand.evaluate_n(6), hand.evaluate());
+            assert_eq!(hand.evaluate_n(5), hand.evaluate());
+        }
+        assert_eq!(evaluate_n("AsKsQsJsTs", 5), (8 << 12) + 9);
+
+        let category = |hand_str: &str, n: usize| get_hand_category(evaluate_n(hand_str, n));
+        assert_eq!(category("AsAcAhAdKs", 4), HandCategory::FourOfAKind);
+        assert_eq!(category("AsAcAhKdKs", 4), HandCategory::ThreeOfAKind);
+        assert_eq!(category("AsAcKhKd2s", 4), HandCategory::TwoPair);
+        assert_eq!(category("AsAcKhQd2s", 3), HandCategory::OnePair);
+        assert_eq!(category("AsKsQsJsTs", 4), HandCategory::HighCard);
+        assert_eq!(category("AsAh", 1), HandCategory::HighCard);
+
+        // best sub-hands
+        assert_eq!(evaluate_n("AsAhKcQd", 3), evaluate_n("AsAhKc", 3));
+        assert_eq!(evaluate_n("2s3h7c9dKs", 2), evaluate_n("Ks9d", 2));
+        assert_eq!(evaluate_n("2s2h3c3d", 2), evaluate_n("3c3d", 2));
+
+        // orderings within and across categories
+        assert!(evaluate_n("AsAhKc", 3) > evaluate_n("AsAhQc", 3));
+        assert!(evaluate_n("AsAhQc", 3) > evaluate_n("KsKhAc", 3));
+        assert!(evaluate_n("2s2h2c", 3) > evaluate_n("AsAhKc", 3));
+        assert!(evaluate_n("AsKhQcJd", 4) > evaluate_n("AsKhQc9d", 4));
+        assert!(evaluate_n("AsKhQc2d", 4) > evaluate_n("AsKhJcTd", 4));
+        assert!(evaluate_n("2s2h3c4d", 4) > evaluate_n("AsKhQcJd", 4));
+        assert!(evaluate_n("3s3h2c2d", 4) > evaluate_n("AsAhKcQd", 4));
+        assert!(evaluate_n("As", 1) > evaluate_n("Ks", 1));
+    }
+
+    #[test]
+    fn test_evaluate_omaha() {
+        let hand = |hand_str: &str| hand_str.parse::<Hand>().unwrap();
+        let omaha = |board: &str, hole: &str| hand(board).evaluate_omaha(hand(hole));
+
+        // one spade in hole cards does not make a flush
+        let board = "AsKsQsJs2d";
+        let hole = "Ts3h4h5c";
+        assert_eq!(
+            get_hand_category((hand(board) + hand(hole)).evaluate()),
+            HandCategory::StraightFlush
+        );
+        assert_eq!(omaha(board, hole), hand("AsKsQsTs5c").evaluate());
+
+        // four of a kind in hole cards counts as two pair
+        let board = "KsKd2c3h7d";
+        let hole = "AsAhAcAd";
+        assert_eq!(get_hand_category(omaha(board, hole)), HandCategory::TwoPair);
+        assert_eq!(omaha(board, hole), hand("AsAhKsKd7d").evaluate());
+
+        // board with 3 and 4 cards
+        assert_eq!(omaha("KsKdKc", "AsAh2c3d"), hand("KsKdKcAsAh").evaluate());
+        assert_eq!(omaha("9s8s7s3c", "TsJs4h4d"), hand("JsTs9s8s7s").evaluate());
+
+        // using all 5 cards of a board is not allowed
+        assert_eq!(
+            omaha("AhKhQhJhTh", "2s2c3s3c"),
+            hand("AhKhQh3s3c").evaluate()
+        );
+    }
+
+    #[test]
+    fn test_evaluate_plo5() {
+        let hand = |hand_str: &str| hand_str.parse::<Hand>().unwrap();
+
+        // the straight flush uses the two lowest hole cards
+        let board = hand("8s9sTs2c2d");
+        assert_eq!(
+            board.evaluate_plo5(hand("AhKdQc7s6s")),
+            hand("6s7s8s9sTs").evaluate()
+        );
+        assert_eq!(
+            board.evaluate_omaha(hand("AhKdQc7s")),
+            hand("AhKdTs2c2d").evaluate()
+        );
+
+        // each hole card combination is considered
+        for hole in &["AhKdQc7s6s", "6sAhKdQc7s", "7sAhKd6sQc"] {
+            assert_eq!(
+                board.evaluate_plo5(hand(hole)),
+                hand("6s7s8s9sTs").evaluate()
+            );
+        }
+
+        // a single hole card does not count
+        let board = hand("AcKcQcJc3d");
+        assert_eq!(
+            board.evaluate_plo5(hand("Tc2h4h5s6d")),
+            hand("AcKcQcTc6d").evaluate()
+        );
+        assert_eq!(
+            board.evaluate_plo5(hand("Tc9c2h4h5s")),
+            hand("KcQcJcTc9c").evaluate()
+        );
+    }
+
+    #[test]
+    fn test_evaluate_omaha_hi_lo() {
+        let hand = |hand_str: &str| hand_str.parse::<Hand>().unwrap();
+        let hi_lo = |board: &str, hole: &str| hand(board).evaluate_omaha_hi_lo(hand(hole));
+
+        // nut low
+        let (high, low) = hi_lo("As2h3c4d5s", "Ah2cKdKs");
+        assert_eq!(high, hand("As2h3c4d5s").evaluate_omaha(hand("Ah2cKdKs")));
+        assert_eq!(low, Some(224));
+        assert_eq!(hi_lo("3c4d5s", "Ah2cKdKs").1, Some(224));
+
+        // 9-high board or less than three low board cards
+        assert_eq!(hi_lo("9sTsJdQhKc", "Ah2c3d4s").1, None);
+        assert_eq!(hi_lo("9s8s7dQhKc", "Ah2c3d4s").1, None);
+
+        // counterfeited low cards and paired hole cards
+        assert_eq!(hi_lo("As2h3cKdQs", "Ah2cJdJs").1, None);
+        assert_eq!(
+            hi_lo("3c4d7sKhKc", "AhAd8s8c").1,
+            hi_lo("3c4d7sKhKc", "Ah8cTsJd").1
+        );
+
+        // lows are compared from the highest card
+        let worst = hi_lo("8s7h6c", "5d4hKsKc").1.unwrap();
+        assert_eq!(worst, 7);
+        assert!(hi_lo("8s7h2c", "5d4h6s6c").1.unwrap() > worst);
+        assert!(hi_lo("7s6h5c", "4d3hTsTc").1.unwrap() > hi_lo("8s3h2c", "Ad4hTsTc").1.unwrap());
+        assert!(hi_lo("6s4h3c", "2dAhTsTc").1.unwrap() < 224);
+    }
+
+    #[test]
+    fn test_evaluate_stud7_hi_lo() {
+        let hand = |hand_str: &str| hand_str.parse::<Hand>().unwrap();
+
+        // wheel plus two high cards: straight flush for high and the nut low
+        let wheel = hand("As2s3s4s5sKhQd");
+        assert_eq!(wheel.evaluate_stud7(), wheel.evaluate());
+        let (high, low) = wheel.evaluate_stud7_hi_lo();
+        assert_eq!(get_hand_category(high), HandCategory::StraightFlush);
+        assert_eq!(low, Some(224));
+        let (high, low) = hand("Ah2c3d4s5hKcKd").evaluate_stud7_hi_lo();
+        assert_eq!(get_hand_category(high), HandCategory::Straight);
+        assert_eq!(low, Some(224));
+
+        // the low uses the five lowest ranks regardless of the high hand
+        assert_eq!(
+            hand("8s7h6c5d4h3s2c").evaluate_stud7_hi_lo().1,
+            hand("6c5d4h3s2cKsKh").evaluate_stud7_hi_lo().1
+        );
+        assert_eq!(hand("As2h3c4d9s9hKc").evaluate_stud7_hi_lo().1, None);
+        assert_eq!(hand("AsAh2c2d3s4h4c").evaluate_stud7_hi_lo().1, None);
+
+        // equals the best low of all 5-card subsets
+        let mut rng = Xorshift64::new(0x9e37_79b9_7f4a_7c15);
+        for _ in 0..10000 {
+            let hand = rng.deal_hand(7);
+            let cards = hand.cards().collect::<Vec<_>>();
+            let mut low = None;
+            for i in 0..3 {
+                for j in (i + 1)..4 {
+                    for k in (j + 1)..5 {
+                        for m in (k + 1)..6 {
+                            for n in (m + 1)..7 {
+                                let five = [cards[i], cards[j], cards[k], cards[m], cards[n]];
+                                low = low.max(Hand::from_slice(&five).eight_or_better_low());
+                            }
+                        }
+                    }
+                }
+            }
+            assert_eq!(hand.evaluate_stud7_hi_lo(), (hand.evaluate(), low));
+        }
+    }
+
+    #[test]
+    fn test_count_nut_outs() {
+        let hand = |hand_str: &str| hand_str.parse::<Hand>().unwrap();
+        let nut_outs = |hero: &str, board: &str, dead: &str| {
+            hand(hero).count_nut_outs(hand(board), CardSet::from(hand(dead)))
+        };
+
+        // the ace of spades completes the nut flush with any spade except the ones that allow a
+        // straight flush (Qs, Js and Ts) or pair the board (2s)
+        assert_eq!(nut_outs("AsJd", "Ks9s4s2h", ""), 5);
+        // the queen and jack of spades are safe when the ten of spades is dead
+        assert_eq!(nut_outs("AsJd", "Ks9s4s2h", "Ts"), 7);
+        // the ace of spades makes the nuts only with the king of spades (a royal flush)
+        assert_eq!(nut_outs("As3d", "TsJsQs2h", ""), 1);
+        // lower flush cards make the nuts only when the higher spades are on the board
+        assert_eq!(nut_outs("QsJd", "Ks9s4s2h", ""), 1);
+        assert_eq!(nut_outs("7s6d", "Ks9s4s2h", ""), 0);
+
+        // the royal flush stays the nuts with any card
+        assert_eq!(nut_outs("AsKs", "QsJsTs", ""), 47);
+        assert_eq!(nut_outs("2c3d", "AsKsQs", ""), 0);
+    }
+
+    #[test]
+    fn test_outs() {
+        let hand = |hand_str: &str| hand_str.parse::<Hand>().unwrap();
+        let outs = |hero: &str, villain: &str, board: &str| {
+            let (hero, villain, board) = (hand(hero), hand(villain), hand(board));
+            let outs = hero.outs(villain, board);
+            assert_eq!(hero.count_outs(villain, board) as usize, outs.len());
+            outs
+        };
+
+        // flush draw vs. made straight on the turn: nine spades
+        let flush_outs = outs("4s3s", "9h8d", "QsTh7sJc");
+        assert_eq!(flush_outs.len(), 9);
+        assert!(flush_outs.iter().all(|card| card.suit() == 0));
+
+        // open-ended straight draw vs. top pair on the flop: four tens and four fives
+        let straight_outs = outs("9h8h", "KcQd", "Ks7d6c");
+        assert_eq!(straight_outs.len(), 8);
+        assert_eq!(Hand::from_slice(&straight_outs), hand("TsThTcTd5s5h5c5d"));
+
+        // already winning or drawing dead
+        assert!(outs("AsAh", "KcKd", "2s7h9c").is_empty());
+        assert!(outs("2c3d", "AsKs", "QsJsTs").is_empty());
+
+        // from a tie, only the three tens make hero win
+        let tie_outs = outs("KdTd", "KcJs", "AsAh7c7d");
+        assert_eq!(Hand::from_slice(&tie_outs), hand("TsThTc"));
+    }
+
+    #[test]
+    fn test_probability_improve_by_river() {
+        let hand = |hand_str: &str| hand_str.parse::<Hand>().unwrap();
+        let probability = |hero: &str, villain: &str, board: &str| {
+            hand(hero).probability_improve_by_river(hand(villain), hand(board))
+        };
+
+        // flush draw vs. made straight: one of nine spades in two cards
+        let flush_draw = probability("4s3s", "9h8d", "Ts7sJc");
+        assert!((flush_draw - (1.0 - 630.0 / 990.0)).abs() < 1e-9);
+
+        // already winning, but villain may catch up
+        let overpair = probability("AsAh", "KcKd", "7h2c9d");
+        assert!(0.9 < overpair && overpair < 1.0);
+        let quads = probability("AsAh", "AcKd", "AdKhKs");
+        assert!((quads - (1.0 - 44.0 / 990.0)).abs() < 1e-9);
+
+        // chopped boards do not count as wins
+        assert_eq!(probability("2s3h", "2c3d", "AsKhQd"), 0.0);
+        let heads_up = crate::heads_up::equity_vs(&hand("AsKh"), &hand("QcQd"), &hand("Qs7h2d"));
+        assert!((probability("AsKh", "QcQd", "Qs7h2d") - heads_up.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_evaluate_badugi() {
+        let badugi = |hand_str: &str| hand_str.parse::<Hand>().unwrap().evaluate_badugi();
+
+        // four-card hands
+        assert_eq!(badugi("As2h3c4d"), 0);
+        assert!(badugi("As2h3c4d") < badugi("As2h3c5d"));
+        assert!(badugi("As2h3c5d") < badugi("As2h4c5d"));
+        assert!(badugi("4s3h2cKd") > badugi("Qs Jh Tc 9d"));
+        assert!(badugi("KsQhJcTd") < badugi("As2h3c4c"));
+
+        // pairs and suited cards are discarded
+        assert_eq!(badugi("As2h3c3d"), badugi("As2h3cKc"));
+        assert!(badugi("As2s3c4d") > badugi("Ks Qh Jc Td"));
+        assert_eq!(badugi("As2s3c4d"), badugi("As5s3c4d"));
+        assert!(badugi("As2s3c4d") < badugi("As5s3c6d"));
+        assert!(badugi("AsAhAc2d") > badugi("Ks Qh Jc Tc"));
+        assert!(badugi("AsAhAc2d") < badugi("As2s3s4s"));
+
+        // the weakest hands
+        assert_eq!(badugi("As2s3s4s"), badugi("AsKsQsJs"));
+        assert!(badugi("As2s3s4s") < badugi("2s3s4s5s"));
+        let weakest = badugi("KsKhKcKd");
+        assert!(weakest > badugi("Qs Qh Qc Qd"));
+        assert!(weakest > badugi("KsKhKcQd"));
+
+        // every four-card hand is at most the weakest hand
+        let mut rankset = HashSet::new();
+        for i in 0..(NUMBER_OF_CARDS - 3) {
+            for j in (i + 1)..(NUMBER_OF_CARDS - 2) {
+                for k in (j + 1)..(NUMBER_OF_CARDS - 1) {
+                    for m in (k + 1)..NUMBER_OF_CARDS {
+                        let rank = Hand::from_slice(&[card(i), card(j), card(k), card(m)])
+                            .evaluate_badugi();
+                        assert!(rank <= weakest);
+                        rankset.insert(rank);
+                    }
+                }
+            }
+        }
+        // C(13, 4) + C(13, 3) + C(13, 2) + C(13, 1)
+        assert_eq!(rankset.len(), 715 + 286 + 78 + 13);
     }
 
-    /// Checks whether the hand is empty.
-    #[inline]
-    pub fn is_empty(&self) -> bool {
-        self.mask == 0
-    }
+    #[test]
+    fn test_evaluate_pai_gow_high_and_low() {
+        let hand = |hand_str: &str| hand_str.parse::<Hand>().unwrap();
+        let split = |hand_str: &str| hand(hand_str).evaluate_pai_gow_high_and_low().unwrap();
 
-    /// Returns current number of cards in `self`.
-    #[inline]
-    pub fn len(&self) -> usize {
-        self.mask.count_ones() as usize
-    }
+        // no pair: the second and third highest cards go low
+        assert_eq!(split("AsKhQc9d7s5h2d"), (hand("As9d7s5h2d"), hand("KhQc")));
 
-    /// Returns the bit mask of `self`.
-    #[inline]
-    pub fn get_mask(&self) -> u64 {
-        self.mask
-    }
+        // one pair: the two highest singletons go low
+        assert_eq!(split("AsAhKdQc9s5h2d"), (hand("AsAh9s5h2d"), hand("KdQc")));
+        assert_eq!(split("9s9h7c6d4s3h2d"), (hand("9s9h4s3h2d"), hand("7c6d")));
 
-    /// Returns whether the `card` is included in `self`.
-    #[inline]
-    pub fn contains(&self, card: usize) -> bool {
-        (self.mask & unsafe { *CARDS.get_unchecked(card) }.1) != 0
+        // two pair: split, unless low pairs can be protected by an ace
+        assert_eq!(split("AsAhKdKc9s5h2d"), (hand("AsAh9s5h2d"), hand("KdKc")));
+        assert_eq!(split("JsJh4d4cAs9h2d"), (hand("JsJhAs9h2d"), hand("4d4c")));
+        assert_eq!(split("8s8h4d4cAs9h2d"), (hand("8s8h4d4c2d"), hand("As9h")));
+        assert_eq!(split("8s8h4d4cKs9h2d"), (hand("8s8hKs9h2d"), hand("4d4c")));
+
+        // three pair: the highest pair goes low
+        assert_eq!(split("AsAhKcKdQsQh2d"), (hand("KcKdQsQh2d"), hand("AsAh")));
+
+        // three of a kind: the trips stay high, but trip aces are split
+        assert_eq!(split("9s9h9dKcQs5h2d"), (hand("9s9h9d5h2d"), hand("KcQs")));
+        let (high, low) = split("AsAhAdKcQs5h2d");
+        assert_eq!(get_hand_category(high.evaluate()), HandCategory::OnePair);
+        assert!(low.contains_rank(12) && low.contains_rank(11));
+        let (high, low) = split("9s9h9d5c5s5h2d");
+        assert_eq!(
+            get_hand_category(high.evaluate()),
+            HandCategory::ThreeOfAKind
+        );
+        assert!(low.contains_rank(7) && low.count_rank(7) == 2);
+
+        // full house: the trips stay high, and the highest pair goes low
+        assert_eq!(split("9s9h9dKcKs5h2d"), (hand("9s9h9d5h2d"), hand("KcKs")));
+        assert_eq!(split("9s9h9dKcKs5h5d"), (hand("9s9h9d5h5d"), hand("KcKs")));
+
+        // four of a kind: the quads stay high with the best low hand
+        assert_eq!(split("9s9h9d9cKsQh2d"), (hand("9s9h9d9c2d"), hand("KsQh")));
+        assert_eq!(split("9s9h9d9cKsKh2d"), (hand("9s9h9d9c2d"), hand("KsKh")));
+
+        // straights and flushes are played high with the best low hand
+        assert_eq!(split("Ts9h8d7c6s5h2d"), (hand("9h8d7c6s5h"), hand("Ts2d")));
+        assert_eq!(split("AsKsQsJsTs3h2d"), (hand("AsKsQsJsTs"), hand("3h2d")));
+        assert_eq!(split("AsKsQsJsTs9sKh"), (hand("AsQsJsTs9s"), hand("KsKh")));
+
+        // the high hand always outranks the low hand
+        for hand_str in &[
+            "AsAhKcKdQsQh2d",
+            "KsKhAcAdQsQh2d",
+            "2s2h3c3d4s4h5d",
+            "AsAhAdAcKsKhKd",
+            "2s2h2d3c3s3h4d",
+            "As2h3d4c5s9h9d",
+        ] {
+            let (high, low) = split(hand_str);
+            assert_eq!(high + low, hand(hand_str));
+            assert_eq!((high.len(), low.len()), (5, 2));
+            assert!(high.outranks_pai_gow_low(&low));
+        }
+        assert!(!hand("9s7h5c3d2s").outranks_pai_gow_low(&hand("AsKd")));
+        assert!(!hand("AsKh5c3d2s").outranks_pai_gow_low(&hand("2c2d")));
+        assert!(hand("AsKh5c3d2s").outranks_pai_gow_low(&hand("AcKd")));
+        assert!(hand("AsAh5c3d2s").outranks_pai_gow_low(&hand("AcAd")));
+        assert_eq!(hand("AsKsQs").evaluate_pai_gow_high_and_low(), None);
     }
 
-    /// Returns a new hand struct where `card` is added to `self`.
-    /// `card` must be in the range \[0, 51\] and must not be already included in `self`.
-    /// (0 corresponds to the deuce of clubs, and 51 corresponds to the ace of spades)
-    #[inline]
-    pub fn add_card(&self, card: usize) -> Self {
-        let (k, m) = unsafe { *CARDS.get_unchecked(card) };
-        Self {
-            key: self.key.wrapping_add(k),
-            mask: self.mask.wrapping_add(m),
+    #[test]
+    fn test_to_card_array() {
+        assert!(Hand::new().to_card_array().is_empty());
+        let hand = "AhKdQc".parse::<Hand>().unwrap();
+        assert_eq!(hand.to_card_array().as_slice(), &[42, 47, 49].map(card)[..]);
+        assert_eq!(hand.to_card_array().len(), 3);
+
+        // steps 1, 3, 5 and 7 are coprime with 52, so the cards below are distinct
+        for len in 0..=7 {
+            for step in (1..=7).step_by(2) {
+                for start in 0..NUMBER_OF_CARDS {
+                    let mut cards = (0..len)
+                        .map(|i| card((start + step * i) % NUMBER_OF_CARDS))
+                        .collect::<Vec<_>>();
+                    cards.sort();
+                    let array = Hand::from_slice(&cards).to_card_array();
+                    assert_eq!(array.as_slice(), &cards[..]);
+                }
+            }
         }
     }
 
-    /// Returns a new hand struct where `card` is removed from `self`.
-    /// `card` must be in the range \[0, 51\] and included in `self`.
-    #[inline]
-    pub fn remove_card(&self, card: usize) -> Self {
-        let (k, m) = unsafe { *CARDS.get_unchecked(card) };
-        Self {
-            key: self.key.wrapping_sub(k),
-            mask: self.mask.wrapping_sub(m),
+    #[test]
+    #[allow(clippy::manual_is_multiple_of)]
+    fn test_normalize() {
+        // all insertion orders of 7 cards give the same hand
+        for hand_str in &["AsKsQsJsTs7d5s", "3d3h3s2c2d2h2s", "AdKdQdJd9s3h2c"] {
+            let hand = hand_str.parse::<Hand>().unwrap();
+            let mut cards = hand.to_card_array().as_slice().to_vec();
+            let mut c = [0; 7];
+            let mut i = 0;
+            // Heap's algorithm
+            while i < 7 {
+                if c[i] < i {
+                    cards.swap(if i % 2 == 0 { 0 } else { c[i] }, i);
+                    let permuted = Hand::from_slice(&cards);
+                    assert_eq!(permuted, hand);
+                    assert_eq!(permuted.normalize(), hand);
+                    c[i] += 1;
+                    i = 0;
+                } else {
+                    c[i] = 0;
+                    i += 1;
+                }
+            }
         }
+        assert_eq!(Hand::new().normalize(), Hand::new());
     }
 
-    /// Returns hand strength in 16-bit integer.
-    /// This function may crush when `self.len() < 5 || self.len() > 7`.
-    #[inline]
-    pub fn evaluate(&self) -> u16 {
-        let is_flush = self.key & FLUSH_MASK;
-        if is_flush > 0 {
-            let flush_key = (self.mask >> (4 * is_flush.leading_zeros())) as u16;
-            unsafe { *LOOKUP_FLUSH.get_unchecked(flush_key as usize) }
-        } else {
-            let rank_key = self.key as u32 as usize;
-            let offset = unsafe { *OFFSETS.get_unchecked(rank_key >> OFFSET_SHIFT) as usize };
-            let hash_key = rank_key.wrapping_add(offset);
-            unsafe { *LOOKUP.get_unchecked(hash_key) }
+    #[test]
+    fn test_flip_suits() {
+        let hand = |hand_str: &str| hand_str.parse::<Hand>().unwrap();
+        assert_eq!(Hand::new().flip_suits(), Hand::new());
+        assert_eq!(hand("AsKhQcJd").flip_suits(), hand("AhKcQdJs"));
+        assert_eq!(hand("AsKs").flip_suits(), hand("AhKh"));
+
+        // 7 and 52 are coprime, so the cards below are distinct
+        for len in 0..=7 {
+            for start in 0..NUMBER_OF_CARDS {
+                let cards = (0..len)
+                    .map(|i| card((start + 7 * i) % NUMBER_OF_CARDS))
+                    .collect::<Vec<_>>();
+                let hand = Hand::from_slice(&cards);
+                let flipped = hand.flip_suits();
+                assert_eq!(flipped.len(), len);
+                assert!(flipped.is_valid());
+                for card in &cards {
+                    let suit = (card.suit() + 1) % 4;
+                    assert!(flipped.contains(Card::new(card.rank(), suit).unwrap()));
+                }
+                if len >= 1 {
+                    assert_ne!(flipped.get_mask(), hand.get_mask());
+                }
+                if len >= 5 {
+                    assert_eq!(flipped.evaluate(), hand.evaluate());
+                }
+                let flipped4 = flipped.flip_suits().flip_suits().flip_suits();
+                assert_eq!(flipped4, hand);
+            }
         }
     }
-}
 
-impl Add for Hand {
-    type Output = Self;
-    fn add(self, rhs: Self) -> Self::Output {
-        Self {
-            key: self
-                .key
-                .wrapping_add(rhs.key)
-                .wrapping_sub(0x3333 << SUIT_SHIFT),
-            mask: self.mask.wrapping_add(rhs.mask),
+    #[test]
+    fn test_split_suits() {
+        let hand = "AsKsQh2c3c4c9d".parse::<Hand>().unwrap();
+        let suits = hand.split_suits();
+        assert_eq!(suits[0], "AsKs".parse().unwrap());
+        assert_eq!(suits[1], "Qh".parse().unwrap());
+        assert_eq!(suits[2], "2c3c4c".parse().unwrap());
+        assert_eq!(suits[3], "9d".parse().unwrap());
+        assert_eq!(Hand::new().split_suits(), [Hand::new(); 4]);
+        assert_eq!(hand.suit_distribution(), [2, 1, 3, 1]);
+        assert_eq!(Hand::new().suit_distribution(), [0; 4]);
+        let hand = "AsKsQsJh2h3c4d".parse::<Hand>().unwrap();
+        assert_eq!(hand.suit_distribution(), [3, 2, 1, 1]);
+
+        for i in 0..(NUMBER_OF_CARDS - 4) {
+            for j in ((i + 1)..(NUMBER_OF_CARDS - 3)).step_by(3) {
+                for k in ((j + 1)..(NUMBER_OF_CARDS - 2)).step_by(5) {
+                    for m in ((k + 1)..NUMBER_OF_CARDS).step_by(7) {
+                        let hand = Hand::from_slice(&[card(i), card(j), card(k), card(m)]);
+                        let suits = hand.split_suits();
+                        assert_eq!(suits.iter().map(Hand::len).sum::<usize>(), hand.len());
+                        assert_eq!(suits.map(|h| h.len() as u8), hand.suit_distribution());
+                        assert_eq!(suits.iter().fold(Hand::new(), |sum, h| sum + *h), hand);
+                        for (suit, sub_hand) in suits.iter().enumerate() {
+                            assert!(sub_hand.cards().all(|c| c.suit() as usize == suit));
+                        }
+                    }
+                }
+            }
         }
     }
-}
 
-impl AddAssign for Hand {
-    fn add_assign(&mut self, rhs: Self) {
-        self.key = self.key.wrapping_add(rhs.key);
-        self.key = self.key.wrapping_sub(0x3333 << SUIT_SHIFT);
-        self.mask = self.mask.wrapping_add(rhs.mask);
+    #[test]
+    fn test_suit_predicates() {
+        let hand = |hand_str: &str| hand_str.parse::<Hand>().unwrap();
+        let predicates = |hand_str: &str| {
+            let hand = hand(hand_str);
+            (hand.is_rainbow(), hand.is_monotone(), hand.is_two_tone())
+        };
+        assert_eq!(predicates("Ac7c6c5c4c3c2c"), (false, true, false));
+        assert_eq!(predicates("AsKsQsJsTs7d5s"), (false, false, true));
+        assert_eq!(predicates("AhKhQhJh9h9c9s"), (false, false, false));
+        assert_eq!(predicates("AsAcAhAdKsQcTh"), (false, false, false));
+        assert_eq!(predicates("AsKhQcJd"), (true, false, false));
+        assert_eq!(predicates("Kd7h"), (true, false, true));
+        assert_eq!(predicates("As"), (true, true, false));
+        assert_eq!(predicates(""), (false, false, false));
     }
-}
 
-impl Default for Hand {
-    fn default() -> Self {
-        Self::new()
+    #[test]
+    fn test_suited_cards() {
+        // two-suited board: 3 spades and 2 hearts
+        let board = "AsKs7s9h2h".parse::<Hand>().unwrap();
+        let pairs = board.suited_cards().collect::<Vec<_>>();
+        assert_eq!(pairs, vec![(1, 29), (20, 44), (20, 48), (44, 48)]);
+        assert!(pairs.iter().all(|(a, b)| a % 4 == b % 4 && a / 4 != b / 4));
+
+        assert_eq!(
+            "AsKhQcJd".parse::<Hand>().unwrap().suited_cards().count(),
+            0
+        );
+        assert_eq!(
+            "AsKsQsJsTs9s8s"
+                .parse::<Hand>()
+                .unwrap()
+                .suited_cards()
+                .count(),
+            21
+        );
+        assert_eq!(Hand::new().suited_cards().count(), 0);
     }
-}
 
-impl FromStr for Hand {
-    type Err = String;
+    #[test]
+    fn test_connected_cards() {
+        let run = "Ts9h8c7d6s".parse::<Hand>().unwrap();
+        let pairs = run.connected_cards().collect::<Vec<_>>();
+        assert_eq!(pairs, vec![(16, 23), (23, 26), (26, 29), (29, 32)]);
+        assert!(pairs.iter().all(|(a, b)| a < b && b / 4 - a / 4 == 1));
 
-    fn from_str(hand_str: &str) -> Result<Self, Self::Err> {
-        let mut hand = Self::new();
-        let mut chars = hand_str.chars();
-        loop {
-            let rank_opt = chars.next();
-            if rank_opt.is_none() {
-                return Ok(hand);
-            }
-            let rank_char = rank_opt.unwrap();
-            let suit_char = chars
-                .next()
-                .ok_or("parse failed: expected suit character, but got EOF")?;
-            let rank_id = match rank_char.to_ascii_uppercase() {
-                '2' => Ok(0),
-                '3' => Ok(1),
-                '4' => Ok(2),
-                '5' => Ok(3),
-                '6' => Ok(4),
-                '7' => Ok(5),
-                '8' => Ok(6),
-                '9' => Ok(7),
-                'T' => Ok(8),
-                'J' => Ok(9),
-                'Q' => Ok(10),
-                'K' => Ok(11),
-                'A' => Ok(12),
-                ch => Err(format!(
-                    "parse failed: expected rank character, but got '{}'",
-                    ch
-                )),
-            }?;
-            let suit_id = match suit_char.to_ascii_lowercase() {
-                's' => Ok(0),
-                'h' => Ok(1),
-                'c' => Ok(2),
-                'd' => Ok(3),
-                ch => Err(format!(
-                    "parse failed: expected suit character, but got '{}'",
-                    ch
-                )),
-            }?;
-            hand = hand.add_card(rank_id * 4 + suit_id);
-        }
+        let broadway = "AsKdQhJcTs".parse::<Hand>().unwrap();
+        assert_eq!(broadway.connected_cards().count(), 4);
+        assert!(broadway.connected_cards().any(|pair| pair == (47, 48)));
+
+        // pairs multiply the connections, and the ace is not adjacent to the deuce
+        assert_eq!(
+            "9s9h8c".parse::<Hand>().unwrap().connected_cards().count(),
+            2
+        );
+        assert_eq!("As2d".parse::<Hand>().unwrap().connected_cards().count(), 0);
+        assert_eq!(Hand::new().connected_cards().count(), 0);
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::collections::HashSet;
+    #[test]
+    fn test_canonical_form() {
+        let hand = |hand_str: &str| hand_str.parse::<Hand>().unwrap();
+        assert_eq!(hand("AsKs").canonical_form(), hand("AcKc").canonical_form());
+        assert_eq!(hand("AsKh").canonical_form(), hand("AdKc").canonical_form());
+        assert_ne!(hand("AsKs").canonical_form(), hand("AsKh").canonical_form());
+        assert_eq!(Hand::new().canonical_form(), Hand::new());
 
-    fn evaluate_hand_str(hand_str: &str) -> u16 {
-        let hand = hand_str.parse::<Hand>().unwrap();
-        assert_eq!(hand.len(), 7);
-        hand.evaluate()
+        // 169 starting hands and 1,755 flops
+        let mut starting_hands = HashSet::new();
+        let mut flops = HashSet::new();
+        for i in 0..NUMBER_OF_CARDS {
+            for j in (i + 1)..NUMBER_OF_CARDS {
+                let two = Hand::from_slice(&[card(i), card(j)]);
+                let canonical = two.canonical_form();
+                assert_eq!(canonical.canonical_form(), canonical);
+                assert_eq!(two.flip_suits().canonical_form(), canonical);
+                starting_hands.insert(canonical);
+                for k in (j + 1)..NUMBER_OF_CARDS {
+                    flops.insert(two.add_card(card(k)).canonical_form());
+                }
+            }
+        }
+        assert_eq!(starting_hands.len(), 169);
+        assert_eq!(flops.len(), 1755);
+
+        let seven = hand("AsKsQhJh9c4d2s");
+        assert_eq!(seven.canonical_form().evaluate(), seven.evaluate());
+        assert_eq!(
+            seven.canonical_form().canonical_form(),
+            seven.canonical_form()
+        );
     }
 
     #[test]
-    fn test_parser() {
-        let cards = [2, 3, 5, 7, 11, 13, 17];
-        let hand_from_vec = Hand::from_slice(&cards);
-        let hand_from_str = "2h2s3d3s4s5d6d".parse::<Hand>();
-        assert_eq!(hand_from_str, Ok(hand_from_vec));
-        assert_eq!("".parse::<Hand>(), Ok(Hand::new()));
+    fn test_evaluate_razz() {
+        let razz = |hand_str: &str| hand_str.parse::<Hand>().unwrap().evaluate_razz();
+        assert_eq!(razz("As2h3c4d5s"), 0);
+        assert_eq!(razz("As2s3s4s5s"), 0);
+        assert_eq!(razz("KsKhQcQdJsAh2c"), razz("As2hJsQcKs"));
+        assert_eq!(razz("AsAhAcAd2s2h2c"), razz("AsAhAc2s2h"));
         assert_eq!(
-            "A".parse::<Hand>(),
-            Err("parse failed: expected suit character, but got EOF".into())
+            get_hand_category(razz("AsAhAcAd2s2h2c")),
+            HandCategory::FullHouse
         );
         assert_eq!(
-            "Ax".parse::<Hand>(),
-            Err("parse failed: expected suit character, but got 'x'".into())
+            get_hand_category(razz("AsAhAcAd2s3h")),
+            HandCategory::ThreeOfAKind
         );
         assert_eq!(
-            "10s".parse::<Hand>(),
-            Err("parse failed: expected rank character, but got '1'".into())
+            get_hand_category(razz("9s9h9c9dKs")),
+            HandCategory::FourOfAKind
+        );
+
+        assert!(razz("As2h3c4d6s") < razz("As2h3c4d7s"));
+        assert!(razz("8s7h6c5d4s") < razz("9s4h3c2dAs"));
+        assert!(razz("KsQhJcTd9s") < razz("AsAh2c3d4s"));
+        assert!(razz("AsAh2c3d4s") < razz("2s2hAc3d4s"));
+        assert!(razz("AsAh2c3d4s") < razz("AsAh2c3d5s"));
+        assert!(razz("KsKhQcQdJs") < razz("2s2h2c3d4s"));
+
+        // for 5-card hands without aces, straights and flushes, the order is the inverse of
+        // `evaluate()`
+        let mut ranks = Vec::new();
+        let mut rankset = HashSet::new();
+        for i in 0..(NUMBER_OF_CARDS - 4) {
+            let hand = Hand::new().add_card(card(i));
+            for j in (i + 1)..(NUMBER_OF_CARDS - 3) {
+                let hand = hand.add_card(card(j));
+                for k in (j + 1)..(NUMBER_OF_CARDS - 2) {
+                    let hand = hand.add_card(card(k));
+                    for m in (k + 1)..(NUMBER_OF_CARDS - 1) {
+                        let hand = hand.add_card(card(m));
+                        for n in (m + 1)..NUMBER_OF_CARDS {
+                            let hand = hand.add_card(card(n));
+                            let razz = hand.evaluate_razz();
+                            rankset.insert(razz);
+                            let rank = hand.evaluate();
+                            let category = get_hand_category(rank);
+                            if !hand.contains_rank(12)
+                                && category != HandCategory::Straight
+                                && category != HandCategory::Flush
+                                && category != HandCategory::StraightFlush
+                            {
+                                ranks.push((razz, rank));
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        assert_eq!(rankset.len(), 6175);
+        ranks.sort_unstable();
+        assert!(ranks.windows(2).all(|w| w[0].1 <= w[1].1));
+    }
+
+    #[test]
+    fn test_evaluate_lowball_a5() {
+        let low = |hand_str: &str| hand_str.parse::<Hand>().unwrap().evaluate_lowball_a5();
+        assert_eq!(low("As2h3c4d5s"), 0);
+        assert_eq!(low("As2s3s4s5s"), 0);
+        assert_eq!(low("Ad2d3d4d5dKsKh"), 0);
+        assert!(low("As2h7c8d9s") < low("KsKh7c8d9s"));
+        assert_eq!(low("KsQhJcTd9s7h"), low("QhJcTd9s7h"));
+
+        let mut counter = [0; HandCategory::StraightFlush as usize + 1];
+        let mut rankset = HashSet::new();
+        for hand in AllHandsIterator::new(5) {
+            let rank = hand.evaluate_lowball_a5();
+            counter[get_hand_category(rank) as usize] += 1;
+            rankset.insert(rank);
+        }
+        assert_eq!(
+            counter,
+            [1317888, 1098240, 123552, 54912, 0, 0, 3744, 624, 0]
+        );
+        assert_eq!(rankset.len(), 1287 + 2860 + 858 + 858 + 156 + 156);
+    }
+
+    #[test]
+    fn test_evaluate_27_triple_draw() {
+        let lowball = |hand_str: &str| hand_str.parse::<Hand>().unwrap().evaluate_27_triple_draw();
+        assert_eq!(lowball("7s5h4c3d2s"), 0);
+        let evaluate = |hand_str: &str| hand_str.parse::<Hand>().unwrap().evaluate();
+        assert_eq!(evaluate("As6h4c3d2s") & 0x0fff, ACE_SIX_INDEX);
+        assert_eq!(evaluate("As6s4s3s2s") & 0x0fff, ACE_SIX_INDEX);
+        assert_eq!(lowball("As6h4c3d2s") & 0x0fff, ACE_SIX_INDEX + 1);
+
+        // strongest and weakest hands, in ascending order of rank
+        let strongest = [
+            "7s5h4c3d2s",
+            "7s6h4c3d2s",
+            "7s6h5c3d2s",
+            "7s6h5c4d2s",
+            "8s5h4c3d2s",
+            "8s6h4c3d2s",
+            "8s6h5c3d2s",
+            "8s6h5c4d2s",
+            "8s6h5c4d3s",
+            "8s7h4c3d2s",
+        ];
+        let weakest = [
+            "AsAhAcAdKs",
+            "6s5s4s3s2s",
+            "7s6s5s4s3s",
+            "8s7s6s5s4s",
+            "9s8s7s6s5s",
+            "Ts9s8s7s6s",
+            "JsTs9s8s7s",
+            "QsJsTs9s8s",
+            "KsQsJsTs9s",
+            "AsKsQsJsTs",
+        ];
+        for (i, hand_str) in strongest.iter().enumerate() {
+            assert_eq!(lowball(hand_str), i as u16);
+        }
+        for pair in weakest.windows(2) {
+            assert!(lowball(pair[0]) < lowball(pair[1]));
+        }
+        assert_eq!(lowball(weakest[9]), (8 << 12) + 9);
+        assert_eq!(lowball(weakest[1]), (8 << 12) + 1);
+
+        // A-5-4-3-2 is ace high, and straights and flushes count against the hand
+        let wheel = lowball("As5h4c3d2s");
+        assert_eq!(get_hand_category(wheel), HandCategory::HighCard);
+        assert!(lowball("KsQhJcTd8s") < wheel);
+        assert!(wheel < lowball("As6h4c3d2s"));
+        assert!(lowball("AsKhQcJd9s") < lowball("2s2h3c4d5s"));
+        assert!(lowball("2s2h3c4d5s") < lowball("6s5h4c3d2s"));
+        assert!(lowball("6s5h4c3d2s") < lowball("8s6s4s3s2s"));
+        assert_eq!(
+            get_hand_category(lowball("As5s4s3s2s")),
+            HandCategory::Flush
         );
+        assert!(lowball("As5s4s3s2s") < lowball("As6s4s3s2s"));
+        assert!(lowball("As5s4s3s2s") > lowball("KsQsJsTs8s"));
+
+        // best 5 cards out of 7
+        assert_eq!(lowball("7s5h4c3d2sAsAh"), 0);
+        assert_eq!(lowball("7s5s4s3s2sKhAh"), lowball("Kh5s4s3s2s"));
+
+        // all 7,462 strengths are distinct among 5-card hands
+        let mut rankset = HashSet::new();
+        for i in 0..(NUMBER_OF_CARDS - 4) {
+            for j in (i + 1)..(NUMBER_OF_CARDS - 3) {
+                for k in (j + 1)..(NUMBER_OF_CARDS - 2) {
+                    for m in (k + 1)..(NUMBER_OF_CARDS - 1) {
+                        let hand = Hand::from_slice(&[i, j, k, m].map(card));
+                        for n in (m + 1)..NUMBER_OF_CARDS {
+                            rankset.insert(hand.add_card(card(n)).evaluate_27_five());
+                        }
+                    }
+                }
+            }
+        }
+        assert_eq!(rankset.len(), 7462);
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Hand::new().to_string(), "");
+        assert_eq!("Ah".parse::<Hand>().unwrap().to_string(), "Ah");
+        assert_eq!("AhKdQc".parse::<Hand>().unwrap().to_string(), "Qc Kd Ah");
+        assert_eq!("2h2s3d".parse::<Hand>().unwrap().to_string(), "2s 2h 3d");
+        assert_eq!("Qc Kd  Ah ".parse::<Hand>(), "AhKdQc".parse::<Hand>());
+
+        // 7 and 52 are coprime, so the cards below are distinct
+        for len in 0..=7 {
+            for start in 0..NUMBER_OF_CARDS {
+                let cards = (0..len)
+                    .map(|i| card((start + 7 * i) % NUMBER_OF_CARDS))
+                    .collect::<Vec<_>>();
+                let hand = Hand::from_slice(&cards);
+                assert_eq!(hand.to_string().parse::<Hand>(), Ok(hand));
+            }
+        }
     }
 
     #[test]
@@ -240,19 +2817,25 @@ mod tests {
         let mut counter = vec![0; HandCategory::StraightFlush as usize + 1];
 
         for i in 0..(NUMBER_OF_CARDS - 4) {
-            let hand = Hand::new().add_card(i);
+            let hand = Hand::new().add_card(card(i));
             for j in (i + 1)..(NUMBER_OF_CARDS - 3) {
-                let hand = hand.add_card(j);
+                let hand = hand.add_card(card(j));
                 for k in (j + 1)..(NUMBER_OF_CARDS - 2) {
-                    let hand = hand.add_card(k);
+                    let hand = hand.add_card(card(k));
                     for m in (k + 1)..(NUMBER_OF_CARDS - 1) {
-                        let hand = hand.add_card(m);
+                        let hand = hand.add_card(card(m));
                         for n in (m + 1)..NUMBER_OF_CARDS {
-                            let hand = hand.add_card(n);
+                            let hand = hand.add_card(card(n));
                             let rank = hand.evaluate();
                             let category = get_hand_category(rank);
                             rankset.insert(rank);
                             counter[category as usize] += 1;
+                            assert!(category.rank_range().contains(&rank));
+                            let five = [i, j, k, m, n].map(card);
+                            assert_eq!(
+                                evaluate_five(five[0], five[1], five[2], five[3], five[4]),
+                                rank
+                            );
                         }
                     }
                 }
@@ -260,6 +2843,11 @@ mod tests {
         }
 
         assert_eq!(rankset.len(), 7462);
+        for category in 0..=(HandCategory::StraightFlush as u16) {
+            let range = get_hand_category(category << 12).rank_range();
+            assert!(range.clone().all(|rank| rankset.contains(&rank)));
+        }
+        assert_eq!(HandCategory::StraightFlush.rank_range().count(), 10);
         assert_eq!(counter[HandCategory::StraightFlush as usize], 40);
         assert_eq!(counter[HandCategory::FourOfAKind as usize], 624);
         assert_eq!(counter[HandCategory::FullHouse as usize], 3744);
@@ -277,17 +2865,17 @@ mod tests {
         let mut counter = vec![0; HandCategory::StraightFlush as usize + 1];
 
         for i in 0..(NUMBER_OF_CARDS - 5) {
-            let hand = Hand::new().add_card(i);
+            let hand = Hand::new().add_card(card(i));
             for j in (i + 1)..(NUMBER_OF_CARDS - 4) {
-                let hand = hand.add_card(j);
+                let hand = hand.add_card(card(j));
                 for k in (j + 1)..(NUMBER_OF_CARDS - 3) {
-                    let hand = hand.add_card(k);
+                    let hand = hand.add_card(card(k));
                     for m in (k + 1)..(NUMBER_OF_CARDS - 2) {
-                        let hand = hand.add_card(m);
+                        let hand = hand.add_card(card(m));
                         for n in (m + 1)..(NUMBER_OF_CARDS - 1) {
-                            let hand = hand.add_card(n);
+                            let hand = hand.add_card(card(n));
                             for p in (n + 1)..NUMBER_OF_CARDS {
-                                let hand = hand.add_card(p);
+                                let hand = hand.add_card(card(p));
                                 let rank = hand.evaluate();
                                 let category = get_hand_category(rank);
                                 rankset.insert(rank);
@@ -317,19 +2905,19 @@ mod tests {
         let mut counter = vec![0; HandCategory::StraightFlush as usize + 1];
 
         for i in 0..(NUMBER_OF_CARDS - 6) {
-            let hand = Hand::new().add_card(i);
+            let hand = Hand::new().add_card(card(i));
             for j in (i + 1)..(NUMBER_OF_CARDS - 5) {
-                let hand = hand.add_card(j);
+                let hand = hand.add_card(card(j));
                 for k in (j + 1)..(NUMBER_OF_CARDS - 4) {
-                    let hand = hand.add_card(k);
+                    let hand = hand.add_card(card(k));
                     for m in (k + 1)..(NUMBER_OF_CARDS - 3) {
-                        let hand = hand.add_card(m);
+                        let hand = hand.add_card(card(m));
                         for n in (m + 1)..(NUMBER_OF_CARDS - 2) {
-                            let hand = hand.add_card(n);
+                            let hand = hand.add_card(card(n));
                             for p in (n + 1)..(NUMBER_OF_CARDS - 1) {
-                                let hand = hand.add_card(p);
+                                let hand = hand.add_card(card(p));
                                 for q in (p + 1)..NUMBER_OF_CARDS {
-                                    let hand = hand.add_card(q);
+                                    let hand = hand.add_card(card(q));
                                     let rank = hand.evaluate();
                                     let category = get_hand_category(rank);
                                     rankset.insert(rank);
@@ -395,6 +2983,206 @@ mod tests {
         assert_eq!(evaluate_hand_str("9h8s7d5d4d3c2d"), (0 << 12) + 48);
     }
 
+    #[test]
+    fn test_evaluate_safe() {
+        let mut hand = Hand::new();
+        assert_eq!(hand.evaluate_safe(), None);
+        for i in 0..8 {
+            hand = hand.add_card(card(i));
+            if 5 <= hand.len() && hand.len() <= 7 {
+                assert_eq!(hand.evaluate_safe(), Some(hand.evaluate()));
+            } else {
+                assert_eq!(hand.evaluate_safe(), None);
+            }
+        }
+    }
+
+    #[test]
+    fn test_evaluate_best_5_of_7() {
+        for hand_str in &["AsKsQsJsTs7d5s", "4h4c3s3c2d2c2h", "9h8s7d5d4d3c2d"] {
+            let hand = hand_str.parse::<Hand>().unwrap();
+            assert_eq!(hand.evaluate_best_5_of_7(), hand.evaluate());
+        }
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic]
+    fn test_evaluate_best_5_of_7_six_cards() {
+        "AsKsQsJsTs7d"
+            .parse::<Hand>()
+            .unwrap()
+            .evaluate_best_5_of_7();
+    }
+
+    #[test]
+    fn test_beats() {
+        let hand = |hand_str: &str| hand_str.parse::<Hand>().unwrap();
+        let board = hand("AsKsQsJs2d");
+        let hero = hand("Ts3c") + board;
+        let villain = hand("AhAd") + board;
+        assert_eq!(hero.beats(&villain), Ordering::Greater);
+        assert_eq!(villain.beats(&hero), Ordering::Less);
+
+        // the board plays for both players
+        let board = hand("AsKdQhJcTs");
+        let hero = hand("2c3d") + board;
+        let villain = hand("4h5h") + board;
+        assert_eq!(hero.beats(&villain), Ordering::Equal);
+        assert_eq!(villain.beats(&hero), Ordering::Equal);
+        assert_eq!(hero.beats(&hero), Ordering::Equal);
+
+        // kickers decide
+        let board = hand("AsAd9c5h2s");
+        assert_eq!(
+            (hand("KcQd") + board).beats(&(hand("KhJd") + board)),
+            Ordering::Greater
+        );
+        assert_eq!(
+            (hand("KcQd") + board).beats(&(hand("KhQh") + board)),
+            Ordering::Equal
+        );
+    }
+
+    #[test]
+    fn test_partial_ord() {
+        let hand = |hand_str: &str| hand_str.parse::<Hand>().unwrap();
+        let royal_flush = hand("AsKsQsJsTs");
+        let ace_high = hand("AsKdQh9c7s");
+        assert!(royal_flush > ace_high);
+        assert!(ace_high < royal_flush);
+        assert!(hand("AsKsQsJsTs2d3d") > hand("2s2h2c2dAs"));
+
+        // the same strength with different cards, and unsupported lengths
+        let (royal_hearts, short) = (hand("AhKhQhJhTh"), hand("AsAh"));
+        assert_eq!(royal_flush.partial_cmp(&royal_hearts), None);
+        assert_eq!(royal_flush.beats(&royal_hearts), Ordering::Equal);
+        assert_eq!(short.partial_cmp(&ace_high), None);
+        assert_eq!(short.partial_cmp(&short), Some(Ordering::Equal));
+    }
+
+    #[test]
+    #[allow(clippy::neg_cmp_op_on_partial_ord)]
+    fn test_sort_by_partial_ord() {
+        // each hand together with its suit-rotated copy of the same strength
+        let mut rng = Xorshift64::new(0x9e37_79b9_7f4a_7c15);
+        let mut hands = Vec::new();
+        for _ in 0..1000 {
+            let hand = rng.deal_hand(7);
+            let rotated = hand.cards().fold(Hand::new(), |rotated, card| {
+                rotated.add_card(Card::new(card.rank(), (card.suit() + 1) % 4).unwrap())
+            });
+            assert_eq!(rotated.evaluate(), hand.evaluate());
+            if rotated != hand {
+                assert_eq!(hand.partial_cmp(&rotated), None);
+                assert!(!(hand < rotated) && !(hand > rotated));
+                assert!(!(hand <= rotated) && !(hand >= rotated));
+            }
+            hands.push(hand);
+            hands.push(rotated);
+        }
+
+        // sorting by `<` agrees with the order of hand ranks, treating tied hands as equal
+        hands.sort_by(|lhs, rhs| {
+            if lhs < rhs {
+                Ordering::Less
+            } else if lhs > rhs {
+                Ordering::Greater
+            } else {
+                Ordering::Equal
+            }
+        });
+        assert!(hands.windows(2).all(|w| w[0].evaluate() <= w[1].evaluate()));
+    }
+
+    #[test]
+    fn test_rank_percentile() {
+        let percentile = |hand_str: &str| hand_str.parse::<Hand>().unwrap().rank_percentile();
+        assert!(percentile("AsKsQsJsTs") > 99.98);
+        assert_eq!(percentile("7h5s4d3c2d"), 0.0);
+        let pair = percentile("QsQhAd5c3d");
+        assert!(40.0 < pair && pair < 60.0);
+        assert!(percentile("AsAhKsKhQs") > percentile("AsAhKsQhJs"));
+        assert_eq!(percentile("AsKsQsJsTs7d5s"), 7461.0 / 7462.0 * 100.0);
+        assert_eq!(
+            percentile("2s2h2c2d3s"),
+            (7462.0 - 10.0 - 156.0) / 7462.0 * 100.0
+        );
+    }
+
+    #[test]
+    fn test_rank_in_category() {
+        let edge_cases = [
+            ("AsKsQsJsTs7d5s", 9),
+            ("AdQsJc5d4d3d2d", 0),
+            ("AsAcAhAdKsQcTh", 155),
+            ("3d3h3s2c2d2h2s", 0),
+            ("AsAdAhKcKdKh2d", 155),
+            ("5h4c3s3c2d2c2h", 0),
+            ("AhKhQhJh9h9c9s", 1276),
+            ("Js7c6d5c4c3c2c", 0),
+            ("AhKcKdKhQcJdTs", 9),
+            ("Ac8c7c5d4d3d2d", 0),
+            ("AsAcAhKhQd5c3s", 857),
+            ("7d5c4c3c2d2s2h", 8),
+            ("AsAhKsKhQsQhJs", 857),
+            ("7c6d5h3s3c2d2h", 3),
+            ("AdAsKhQdJs3s2c", 2859),
+            ("8s7s5h4c3c2d2c", 18),
+            ("AdKdQdJd9s3h2c", 1276),
+            ("9h8s7d5d4d3c2d", 48),
+        ];
+        for (hand_str, expected) in &edge_cases {
+            let hand = hand_str.parse::<Hand>().unwrap();
+            assert_eq!(hand.rank_in_category(), *expected);
+            assert_eq!(hand.rank_in_category(), hand.evaluate() & 0x0fff);
+        }
+    }
+
+    #[test]
+    fn test_with_cards() {
+        for n in 0..=7 {
+            assert_eq!(Hand::with_cards(n), Hand::new());
+        }
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn test_deal_random() {
+        use rand::{rngs::StdRng, SeedableRng};
+
+        let mut rng = StdRng::seed_from_u64(0);
+        assert_eq!(Hand::deal_random(0, &mut rng), Hand::new());
+
+        let mut rank_count = [0; NUMBER_OF_RANKS];
+        let iterations = 100_000;
+        for _ in 0..iterations {
+            let hand = Hand::deal_random(7, &mut rng);
+            assert_eq!(hand.len(), 7);
+            assert!(hand.is_valid());
+            for card in hand.cards() {
+                rank_count[card.rank() as usize] += 1;
+            }
+        }
+
+        // chi-squared test with 12 degrees of freedom (critical value 32.91 at p = 0.001)
+        let expected = (7 * iterations) as f64 / NUMBER_OF_RANKS as f64;
+        let chi_squared = rank_count
+            .iter()
+            .map(|count| (*count as f64 - expected).powi(2) / expected)
+            .sum::<f64>();
+        assert!(chi_squared < 32.91);
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    #[should_panic]
+    fn test_deal_random_too_many() {
+        use rand::{rngs::StdRng, SeedableRng};
+
+        Hand::deal_random(8, &mut StdRng::seed_from_u64(0));
+    }
+
     #[test]
     fn test_hand_addition() {
         let hand1 = "4h4c".parse::<Hand>().unwrap();
@@ -403,4 +3191,50 @@ mod tests {
         assert_eq!((hand1 + board).evaluate(), (6 << 12) + 1);
         assert_eq!((hand2 + board).evaluate(), (6 << 12) + 0);
     }
+
+    #[test]
+    fn test_remove_cards() {
+        let cards = [3, 8, 17, 26, 30, 41, 51].map(card);
+        let hand = Hand::from_slice(&cards);
+        assert_eq!(hand.remove_cards(&[]), hand);
+        assert_eq!(hand.remove_cards(&cards), Hand::new());
+        for split in 0..=cards.len() {
+            let (left, right) = cards.split_at(split);
+            assert_eq!(hand.remove_cards(left), Hand::from_slice(right));
+            assert_eq!(hand.remove_cards(right), Hand::from_slice(left));
+        }
+        assert_eq!(
+            hand.remove_cards(&[cards[5], cards[0]]),
+            Hand::from_slice(&[cards[1], cards[2], cards[3], cards[4], cards[6]])
+        );
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic]
+    fn test_remove_cards_absent() {
+        let hand = "AsKsQs".parse::<Hand>().unwrap();
+        hand.remove_cards(&[card(51), card(0)]);
+    }
+
+    #[test]
+    fn test_hand_subtraction() {
+        let hand = "4h4c".parse::<Hand>().unwrap();
+        let board = "3s3c2d2c2h".parse::<Hand>().unwrap();
+        assert_eq!((hand + board) - board, hand);
+        assert_eq!((hand + board) - hand, board);
+        assert_eq!(hand - hand, Hand::new());
+
+        // deals a random hole and board in each iteration
+        let mut rng = Xorshift64::new(0x2545_f491_4f6c_dd1d);
+        for _ in 0..1000 {
+            let cards = rng.deal(7);
+            let hole = Hand::from_slice(&cards[..2]);
+            let board = Hand::from_slice(&cards[2..]);
+            assert_eq!((hole + board) - board, hole);
+            let mut hand = hole + board;
+            hand -= hole;
+            assert_eq!(hand, board);
+        }
+    }
 }
@@ -0,0 +1,150 @@
+use crate::card::*;
+use crate::hand::*;
+use std::convert::TryFrom;
+
+/// A modification of a hand recorded by `HandHistory`, holding a card index in the range
+/// \[0, 51\].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Op {
+    /// `add_card()` of the card
+    Add(usize),
+    /// `remove_card()` of the card
+    Remove(usize),
+}
+
+impl Op {
+    /// Applies `self` to `hand`.
+    #[inline]
+    fn apply(&self, hand: Hand) -> Hand {
+        match *self {
+            Op::Add(card) => hand.add_card(Card::try_from(card).unwrap()),
+            Op::Remove(card) => hand.remove_card(Card::try_from(card).unwrap()),
+        }
+    }
+
+    /// Returns the operation that cancels `self`.
+    #[inline]
+    fn inverse(&self) -> Self {
+        match *self {
+            Op::Add(card) => Op::Remove(card),
+            Op::Remove(card) => Op::Add(card),
+        }
+    }
+}
+
+/// A hand that records every modification made to it, so that they can be replayed or undone.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct HandHistory {
+    hand: Hand,
+    ops: Vec<Op>,
+}
+
+impl HandHistory {
+    /// Creates a new empty hand without history.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the current hand.
+    #[inline]
+    pub fn hand(&self) -> Hand {
+        self.hand
+    }
+
+    /// Returns the recorded operations, the oldest first.
+    #[inline]
+    pub fn ops(&self) -> &[Op] {
+        &self.ops
+    }
+
+    /// Adds `card` to the current hand, records it, and returns the new hand.
+    /// `card` must not be already included in the current hand.
+    #[inline]
+    pub fn add_card(&mut self, card: Card) -> Hand {
+        self.push(Op::Add(card.index()))
+    }
+
+    /// Removes `card` from the current hand, records it, and returns the new hand.
+    /// `card` must be included in the current hand.
+    #[inline]
+    pub fn remove_card(&mut self, card: Card) -> Hand {
+        self.push(Op::Remove(card.index()))
+    }
+
+    /// Rebuilds the current hand by applying the recorded operations to an empty hand.
+    pub fn replay(&self) -> Hand {
+        self.ops.iter().fold(Hand::new(), |hand, op| op.apply(hand))
+    }
+
+    /// Cancels the last `steps` operations, removes them from the history, and returns the new
+    /// hand. This function panics when `steps` exceeds the number of recorded operations.
+    pub fn undo(&mut self, steps: usize) -> Hand {
+        assert!(steps <= self.ops.len());
+        for op in self.ops.drain(self.ops.len() - steps..).rev() {
+            self.hand = op.inverse().apply(self.hand);
+        }
+        self.hand
+    }
+
+    #[inline]
+    fn push(&mut self, op: Op) -> Hand {
+        self.hand = op.apply(self.hand);
+        self.ops.push(op);
+        self.hand
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn card(card_str: &str) -> Card {
+        card_str.parse().unwrap()
+    }
+
+    #[test]
+    fn test_undo() {
+        let mut history = HandHistory::new();
+        history.add_card(card("As"));
+        history.add_card(card("Kh"));
+        history.add_card(card("7d"));
+        assert_eq!(history.hand().len(), 3);
+
+        let hand = history.undo(1);
+        assert_eq!(hand.len(), 2);
+        assert_eq!(hand, "AsKh".parse().unwrap());
+        assert_eq!(history.ops(), &[Op::Add(48), Op::Add(45)]);
+
+        history.remove_card(card("As"));
+        assert_eq!(history.hand(), "Kh".parse().unwrap());
+        assert_eq!(history.undo(1), "AsKh".parse().unwrap());
+        assert_eq!(history.undo(0), "AsKh".parse().unwrap());
+        assert_eq!(history.undo(2), Hand::new());
+        assert!(history.ops().is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_undo_too_many() {
+        let mut history = HandHistory::new();
+        history.add_card(card("As"));
+        history.undo(2);
+    }
+
+    #[test]
+    fn test_replay() {
+        let mut history = HandHistory::new();
+        assert_eq!(history.replay(), Hand::new());
+        for card_str in &["2s", "Td", "Qh", "Ac"] {
+            history.add_card(card(card_str));
+        }
+        history.remove_card(card("Td"));
+        history.add_card(card("9c"));
+
+        let direct = "2sQhAc9c".parse::<Hand>().unwrap();
+        assert_eq!(history.hand(), direct);
+        assert_eq!(history.replay(), direct);
+        assert_eq!(history.ops().len(), 6);
+    }
+}
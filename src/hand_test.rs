@@ -1,7 +1,12 @@
 #[cfg(test)]
 mod tests {
-    use crate::Hand;
+    use crate::{Card, Hand};
     use assets::constants::NUMBER_OF_CARDS;
+    use std::convert::TryFrom;
+
+    fn card(index: usize) -> Card {
+        Card::try_from(index).unwrap()
+    }
 
     fn msb(x: u32) -> u32 {
         1 << (x.leading_zeros() ^ 31)
@@ -105,26 +110,26 @@ mod tests {
         let mut hand_array = [0; 7];
         for i in 0..(NUMBER_OF_CARDS - 6) {
             hand_array[0] = i;
-            let hand = Hand::new().add_card(i);
+            let hand = Hand::new().add_card(card(i));
             for j in (i + 1)..(NUMBER_OF_CARDS - 5) {
                 hand_array[1] = j;
-                let hand = hand.add_card(j);
+                let hand = hand.add_card(card(j));
                 for k in (j + 1)..(NUMBER_OF_CARDS - 4) {
                     hand_array[2] = k;
-                    let hand = hand.add_card(k);
+                    let hand = hand.add_card(card(k));
                     for m in (k + 1)..(NUMBER_OF_CARDS - 3) {
                         hand_array[3] = m;
-                        let hand = hand.add_card(m);
+                        let hand = hand.add_card(card(m));
                         for n in (m + 1)..(NUMBER_OF_CARDS - 2) {
                             hand_array[4] = n;
-                            let hand = hand.add_card(n);
+                            let hand = hand.add_card(card(n));
                             for p in (n + 1)..(NUMBER_OF_CARDS - 1) {
                                 hand_array[5] = p;
-                                let hand = hand.add_card(p);
+                                let hand = hand.add_card(card(p));
                                 for q in (p + 1)..NUMBER_OF_CARDS {
                                     hand_array[6] = q;
                                     let rank_naive = evaluate_hand_naive(&hand_array);
-                                    let hand = hand.add_card(q);
+                                    let hand = hand.add_card(card(q));
                                     let rank = hand.evaluate();
                                     if table[rank as usize] == 0 {
                                         table[rank as usize] = rank_naive;
@@ -1,6 +1,13 @@
+use crate::card::*;
+use crate::card_set::*;
 use crate::hand::*;
+use crate::range::*;
 use assets::constants::*;
 use assets::heads_up::HEADS_UP_WIN_FREQUENCY;
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+
+type HeadsUpFn = fn(&Hand, &Hand, &[Card]) -> (u32, u32, u32);
 
 /// Computes heads-up win frequency.
 /// Return value: (# of `hand1` wins, # of `hand2` wins, # of tie)
@@ -12,7 +19,7 @@ pub fn heads_up_win_frequency(
 ) -> (u32, u32, u32) {
     assert_eq!(hand1.len(), 2);
     assert!(hand2.len() <= 2);
-    assert!(board.len() == 0 || board.len() == 3 || board.len() == 4 || board.len() == 5);
+    assert!(board.is_empty() || board.len() == 3 || board.len() == 4 || board.len() == 5);
     assert_eq!(
         (*hand1 + *hand2 + *board + *dead_cards).len(),
         hand1.len() + hand2.len() + board.len() + dead_cards.len()
@@ -43,11 +50,165 @@ pub fn heads_up_win_frequency(
     }
 }
 
-fn compute_alive_cards(mask: u64) -> Vec<usize> {
+/// Computes heads-up equity of `hero` against `villain` by enumerating all runouts of `board`.
+/// Return value: (probability of `hero` win, probability of `villain` win, probability of tie)
+pub fn equity_vs(hero: &Hand, villain: &Hand, board: &Hand) -> (f64, f64, f64) {
+    assert_eq!(villain.len(), 2);
+    let (win, lose, tie) = heads_up_win_frequency(hero, villain, board, &Hand::new());
+    let total = (win + lose + tie) as f64;
+    (win as f64 / total, lose as f64 / total, tie as f64 / total)
+}
+
+/// Returns the showdown result of `hero` against `villain` on the river `board` (5 cards): 1.0 when
+/// `hero` wins, 0.5 on a tie, and 0.0 when `hero` loses. Before the river, use `equity_vs()` to
+/// take all runouts into account.
+pub fn relative_hand_strength(hero: Hand, villain: Hand, board: Hand) -> f64 {
+    assert_eq!(board.len(), 5);
+    match (hero + board).beats(&(villain + board)) {
+        Ordering::Greater => 1.0,
+        Ordering::Equal => 0.5,
+        Ordering::Less => 0.0,
+    }
+}
+
+/// Computes the current hand strength of `hero` on `board` (3-5 cards), i.e., the fraction of
+/// all villain's two-card hands that `hero` beats, counting ties as half.
+/// Villain's hands containing any card of `hero`, `board` or `dead` are excluded.
+pub fn hand_strength(hero: Hand, board: Hand, dead: CardSet) -> f64 {
+    assert_eq!(hero.len(), 2);
+    assert!(3 <= board.len() && board.len() <= 5);
+    assert_eq!((hero + board).len(), hero.len() + board.len());
+    let alive_cards =
+        compute_alive_cards(hero.get_mask() | board.get_mask() | dead.get_hand_mask());
+    let hero_rank = (hero + board).evaluate();
+    let len = alive_cards.len();
+    let (mut score, mut total) = (0.0, 0.0);
+    for i in 0..len {
+        let villain = board.add_card(alive_cards[i]);
+        for j in (i + 1)..len {
+            let villain_rank = villain.add_card(alive_cards[j]).evaluate();
+            if hero_rank > villain_rank {
+                score += 1.0;
+            } else if hero_rank == villain_rank {
+                score += 0.5;
+            }
+            total += 1.0;
+        }
+    }
+    score / total
+}
+
+/// Returns the matrix of hand ranks for all hole cards on `board` (3-5 cards): the entry `[i][j]`
+/// is the hand rank of the hole cards of card indices `i` and `j` combined with `board`.
+/// The matrix is symmetric, and the entries are `0` on the diagonal and when `i` or `j` is a
+/// board card. Note that `0` is also the rank of 7-5-4-3-2 high, which only 3-card boards allow.
+pub fn hand_matrix(board: Hand) -> [[u16; NUMBER_OF_CARDS]; NUMBER_OF_CARDS] {
+    assert!(3 <= board.len() && board.len() <= 5);
+    let alive_cards = compute_alive_cards(board.get_mask());
+    let mut matrix = [[0; NUMBER_OF_CARDS]; NUMBER_OF_CARDS];
+    let len = alive_cards.len();
+    for i in 0..len {
+        let hand = board.add_card(alive_cards[i]);
+        for j in (i + 1)..len {
+            let rank = hand.add_card(alive_cards[j]).evaluate();
+            let (c0, c1) = (alive_cards[i].index(), alive_cards[j].index());
+            matrix[c0][c1] = rank;
+            matrix[c1][c0] = rank;
+        }
+    }
+    matrix
+}
+
+/// Hand ranks of all hole cards on a fixed board, created by `precompute_board_equity_table()`.
+#[derive(Clone, Debug)]
+pub struct EquityTable {
+    board: Hand,
+    ranks: Box<[[u16; NUMBER_OF_CARDS]; NUMBER_OF_CARDS]>,
+}
+
+impl EquityTable {
+    /// Returns the board of `self`.
+    #[inline]
+    pub fn board(&self) -> Hand {
+        self.board
+    }
+
+    /// Returns the hand rank of the hole cards `c0` and `c1` combined with the board, or `None`
+    /// when `c0 == c1` or either card is on the board.
+    #[inline]
+    pub fn rank(&self, c0: Card, c1: Card) -> Option<u16> {
+        if c0 == c1 || self.board.contains(c0) || self.board.contains(c1) {
+            None
+        } else {
+            Some(self.ranks[c0.index()][c1.index()])
+        }
+    }
+}
+
+/// Computes the hand ranks of all hole cards on `board` (3-5 cards) in a single pass, so that
+/// they are looked up later by `EquityTable::rank()` without evaluation.
+pub fn precompute_board_equity_table(board: Hand) -> EquityTable {
+    EquityTable {
+        board,
+        ranks: Box::new(hand_matrix(board)),
+    }
+}
+
+/// Returns the minimum equity needed to call `bet` profitably, i.e., `bet / (pot + bet)`, where
+/// `pot` includes all the bets so far (including the bet to be called).
+#[inline]
+pub fn pot_odds_needed(pot: u64, bet: u64) -> f64 {
+    bet as f64 / (pot + bet) as f64
+}
+
+/// Returns whether `hero` should call `bet` into `pot` (see `pot_odds_needed()`) against
+/// `villain_range` on `board`, i.e., whether hero's equity against the range exceeds the pot odds.
+/// Ties count as half a win, and villain's combinations blocked by `hero` or `board` are ignored.
+pub fn should_call(hero: Hand, villain_range: &HandRange, board: Hand, pot: u64, bet: u64) -> bool {
+    range_equity(&hero, villain_range, &board) > pot_odds_needed(pot, bet)
+}
+
+/// Computes the equity of `hero` against the combinations of `range` not blocked by `hero` or
+/// `board`, weighting each combination equally and counting ties as half.
+fn range_equity(hero: &Hand, range: &HandRange, board: &Hand) -> f64 {
+    let (win, _, tie) = hero.equity_vs_range(*board, range);
+    win + 0.5 * tie
+}
+
+impl Hand {
+    /// Computes exact heads-up equity of `self` (2 cards) against `range` by enumerating all
+    /// runouts of `board` (0, 3, 4 or 5 cards) for each combination of `range`, where the
+    /// combinations blocked by `self` or `board` are ignored and the others are weighted equally.
+    /// Return value: (probability of `self` win, probability of villain win, probability of tie)
+    pub fn equity_vs_range(&self, board: Hand, range: &HandRange) -> (f64, f64, f64) {
+        let mask = self.get_mask() | board.get_mask();
+        let range = range.remove_dead_cards(CardSet::from(*self + board));
+        assert!(!range.is_empty());
+        let num_alive = compute_alive_cards(mask).len();
+        let (win, lose, tie) = if range.len() == num_alive * (num_alive - 1) / 2 {
+            // every combination is included, so enumerate villain's cards at once
+            let freq = heads_up_win_frequency(self, &Hand::new(), &board, &Hand::new());
+            (freq.0 as u64, freq.1 as u64, freq.2 as u64)
+        } else {
+            range.hands().fold((0, 0, 0), |sum, villain| {
+                let freq = heads_up_win_frequency(self, &villain, &board, &Hand::new());
+                (
+                    sum.0 + freq.0 as u64,
+                    sum.1 + freq.1 as u64,
+                    sum.2 + freq.2 as u64,
+                )
+            })
+        };
+        let total = (win + lose + tie) as f64;
+        (win as f64 / total, lose as f64 / total, tie as f64 / total)
+    }
+}
+
+fn compute_alive_cards(mask: u64) -> Vec<Card> {
     let mut result = Vec::new();
     for i in 0..NUMBER_OF_CARDS {
         if (CARDS[i].1 & mask) == 0 {
-            result.push(i);
+            result.push(Card::try_from(i).unwrap());
         }
     }
     result
@@ -74,8 +235,8 @@ fn heads_up_win_freq_0_0(hand: &Hand) -> (u32, u32, u32) {
 fn heads_up_win_freq_0(
     hand1: &Hand,
     hand2: &Hand,
-    alive_cards: &[usize],
-    func: fn(&Hand, &Hand, &[usize]) -> (u32, u32, u32),
+    alive_cards: &[Card],
+    func: HeadsUpFn,
 ) -> (u32, u32, u32) {
     let len = alive_cards.len();
     let mut result = (0, 0, 0);
@@ -90,7 +251,7 @@ fn heads_up_win_freq_0(
                     _ if idx == i || idx == j => None,
                     _ => Some(*x),
                 })
-                .collect::<Vec<usize>>();
+                .collect::<Vec<Card>>();
             let tmp = func(hand1, &hand2, &alive_cards);
             result.0 += tmp.0;
             result.1 += tmp.1;
@@ -103,8 +264,8 @@ fn heads_up_win_freq_0(
 fn heads_up_win_freq_1(
     hand1: &Hand,
     hand2: &Hand,
-    alive_cards: &[usize],
-    func: fn(&Hand, &Hand, &[usize]) -> (u32, u32, u32),
+    alive_cards: &[Card],
+    func: HeadsUpFn,
 ) -> (u32, u32, u32) {
     let len = alive_cards.len();
     let mut result = (0, 0, 0);
@@ -117,7 +278,7 @@ fn heads_up_win_freq_1(
                 _ if idx == i => None,
                 _ => Some(*x),
             })
-            .collect::<Vec<usize>>();
+            .collect::<Vec<Card>>();
         let tmp = func(hand1, &hand2, &alive_cards);
         result.0 += tmp.0;
         result.1 += tmp.1;
@@ -126,7 +287,7 @@ fn heads_up_win_freq_1(
     result
 }
 
-fn heads_up_win_freq_2_0(hand1: &Hand, hand2: &Hand, alive_cards: &[usize]) -> (u32, u32, u32) {
+fn heads_up_win_freq_2_0(hand1: &Hand, hand2: &Hand, alive_cards: &[Card]) -> (u32, u32, u32) {
     let len = alive_cards.len();
     let mut count = (0, 0, 0);
     for i in 0..(len - 4) {
@@ -161,7 +322,7 @@ fn heads_up_win_freq_2_0(hand1: &Hand, hand2: &Hand, alive_cards: &[usize]) -> (
     count
 }
 
-fn heads_up_win_freq_2_3(hand1: &Hand, hand2: &Hand, alive_cards: &[usize]) -> (u32, u32, u32) {
+fn heads_up_win_freq_2_3(hand1: &Hand, hand2: &Hand, alive_cards: &[Card]) -> (u32, u32, u32) {
     let len = alive_cards.len();
     let mut count = (0, 0, 0);
     for i in 0..(len - 1) {
@@ -184,7 +345,7 @@ fn heads_up_win_freq_2_3(hand1: &Hand, hand2: &Hand, alive_cards: &[usize]) -> (
     count
 }
 
-fn heads_up_win_freq_2_4(hand1: &Hand, hand2: &Hand, alive_cards: &[usize]) -> (u32, u32, u32) {
+fn heads_up_win_freq_2_4(hand1: &Hand, hand2: &Hand, alive_cards: &[Card]) -> (u32, u32, u32) {
     let len = alive_cards.len();
     let mut count = (0, 0, 0);
     for i in 0..len {
@@ -203,7 +364,7 @@ fn heads_up_win_freq_2_4(hand1: &Hand, hand2: &Hand, alive_cards: &[usize]) -> (
     count
 }
 
-fn heads_up_win_freq_2_5(hand1: &Hand, hand2: &Hand, _: &[usize]) -> (u32, u32, u32) {
+fn heads_up_win_freq_2_5(hand1: &Hand, hand2: &Hand, _: &[Card]) -> (u32, u32, u32) {
     let rank1 = hand1.evaluate();
     let rank2 = hand2.evaluate();
     if rank1 > rank2 {
@@ -214,3 +375,215 @@ fn heads_up_win_freq_2_5(hand1: &Hand, hand2: &Hand, _: &[usize]) -> (u32, u32,
         (0, 0, 1)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn equity_vs_str(hero: &str, villain: &str, board: &str) -> (f64, f64, f64) {
+        let hero = hero.parse::<Hand>().unwrap();
+        let villain = villain.parse::<Hand>().unwrap();
+        let board = board.parse::<Hand>().unwrap();
+        equity_vs(&hero, &villain, &board)
+    }
+
+    #[test]
+    fn test_hand_strength() {
+        let hand = |hand_str: &str| hand_str.parse::<Hand>().unwrap();
+
+        // top set on a rainbow board loses to no hand
+        assert_eq!(
+            hand_strength(hand("AsAh"), hand("AcKd2s"), CardSet::new()),
+            1.0
+        );
+        let hs = hand_strength(hand("AsKh"), hand("Ac7d2s"), CardSet::new());
+        assert!(0.95 < hs && hs < 0.99);
+
+        // nut straight is only tied by other nut straights
+        let board = hand("QsJhTc3d2s");
+        let hs = hand_strength(hand("AsKd"), board, CardSet::new());
+        assert!((hs - (1.0 - 0.5 * 9.0 / 990.0)).abs() < 1e-9);
+
+        // dead cards are removed from villain's hands
+        let dead = CardSet::from(hand("AcAd"));
+        let hs = hand_strength(hand("AsKd"), board, dead);
+        assert!((hs - (1.0 - 0.5 * 3.0 / 903.0)).abs() < 1e-9);
+        assert_eq!(
+            hand_strength(hand("4s3s"), hand("AsKsQsJsTs"), CardSet::new()),
+            0.5
+        );
+    }
+
+    #[test]
+    fn test_relative_hand_strength() {
+        let hand = |hand_str: &str| hand_str.parse::<Hand>().unwrap();
+        let board = hand("Ks9s5d2c2h");
+        assert_eq!(
+            relative_hand_strength(hand("AsKh"), hand("QsQh"), board),
+            1.0
+        );
+        assert_eq!(
+            relative_hand_strength(hand("QsQh"), hand("AsKh"), board),
+            0.0
+        );
+        assert_eq!(
+            relative_hand_strength(hand("AsKh"), hand("AhKd"), board),
+            0.5
+        );
+
+        // the board plays for both players
+        let board = hand("AsKsQsJsTs");
+        assert_eq!(
+            relative_hand_strength(hand("2c3d"), hand("AhAd"), board),
+            0.5
+        );
+    }
+
+    #[test]
+    fn test_hand_matrix() {
+        let board = "AsKd7h2c".parse::<Hand>().unwrap();
+        let matrix = hand_matrix(board);
+        for i in 0..NUMBER_OF_CARDS {
+            assert_eq!(matrix[i][i], 0);
+            for j in 0..NUMBER_OF_CARDS {
+                assert_eq!(matrix[i][j], matrix[j][i]);
+                let (c0, c1) = (Card::try_from(i).unwrap(), Card::try_from(j).unwrap());
+                if board.contains(c0) || board.contains(c1) {
+                    assert_eq!(matrix[i][j], 0);
+                } else if i != j {
+                    let hole = Hand::from_slice(&[c0, c1]);
+                    assert_eq!(matrix[i][j], (hole + board).evaluate());
+                }
+            }
+        }
+        let aa = "AhAc".parse::<Hand>().unwrap();
+        assert_eq!(
+            get_hand_category(matrix[50][49]),
+            HandCategory::ThreeOfAKind
+        );
+        assert_eq!(matrix[50][49], (aa + board).evaluate());
+    }
+
+    #[test]
+    fn test_precompute_board_equity_table() {
+        let board = "Ts9s2d".parse::<Hand>().unwrap();
+        let table = precompute_board_equity_table(board);
+        assert_eq!(table.board(), board);
+        let card = |i: usize| Card::try_from(i).unwrap();
+        for i in (0..NUMBER_OF_CARDS).step_by(3) {
+            for j in (0..NUMBER_OF_CARDS).step_by(5) {
+                let (c0, c1) = (card(i), card(j));
+                if i == j || board.contains(c0) || board.contains(c1) {
+                    assert_eq!(table.rank(c0, c1), None);
+                } else {
+                    let expected = board.add_card(c0).add_card(c1).evaluate();
+                    assert_eq!(table.rank(c0, c1), Some(expected));
+                    assert_eq!(table.rank(c1, c0), Some(expected));
+                }
+            }
+        }
+        let qj = "QsJs".parse::<Hand>().unwrap().cards().collect::<Vec<_>>();
+        let rank = table.rank(qj[0], qj[1]).unwrap();
+        assert_eq!(get_hand_category(rank), HandCategory::HighCard);
+    }
+
+    #[test]
+    fn test_should_call() {
+        assert_eq!(pot_odds_needed(150, 50), 0.25);
+        assert_eq!(pot_odds_needed(100, 100), 0.5);
+
+        // a flush draw with two cards to come has about 35% equity against a made straight
+        let hero = "4s3s".parse::<Hand>().unwrap();
+        let board = "Ts7sJc".parse::<Hand>().unwrap();
+        let villain = "98o".parse::<HandRange>().unwrap();
+        let equity = range_equity(&hero, &villain, &board);
+        assert!(0.3 < equity && equity < 0.4);
+        assert!(should_call(hero, &villain, board, 150, 50));
+        assert!(!should_call(hero, &villain, board, 150, 150));
+
+        // villain's combinations are weighted equally
+        let range = "AA,KK".parse::<HandRange>().unwrap();
+        let hero = "QsQh".parse::<Hand>().unwrap();
+        let board = "Ac7d2s".parse::<Hand>().unwrap();
+        let aa = range_equity(&hero, &"AA".parse().unwrap(), &board);
+        let kk = range_equity(&hero, &"KK".parse().unwrap(), &board);
+        let expected = (3.0 * aa + 6.0 * kk) / 9.0;
+        assert!((range_equity(&hero, &range, &board) - expected).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_equity_vs_range() {
+        let hand = |hand_str: &str| hand_str.parse::<Hand>().unwrap();
+        let mut all_hands = HandRange::new();
+        for i in 0..NUMBER_OF_CARDS {
+            for j in (i + 1)..NUMBER_OF_CARDS {
+                all_hands.add_combo(Card::try_from(i).unwrap(), Card::try_from(j).unwrap());
+            }
+        }
+
+        // AA wins about 85% against a random hand
+        let (win, lose, tie) = hand("AsAh").equity_vs_range(Hand::new(), &all_hands);
+        assert!(0.84 < win && win < 0.86);
+        assert!(tie < 0.01);
+        assert!((win + lose + tie - 1.0).abs() < 1e-9);
+
+        // a range of a single combination is the same as `equity_vs()`
+        let board = hand("Ks5s2d");
+        let (hero, villain) = (hand("8s7s"), hand("KhQd"));
+        let range = "KhQd".parse::<HandRange>().unwrap();
+        let expected = equity_vs(&hero, &villain, &board);
+        let actual = hero.equity_vs_range(board, &range);
+        assert!((actual.0 - expected.0).abs() < 1e-9);
+        assert!((actual.1 - expected.1).abs() < 1e-9);
+        assert!((actual.2 - expected.2).abs() < 1e-9);
+
+        // the full range is the average over the unblocked combinations
+        let villains = all_hands
+            .hands()
+            .filter(|villain| !villain.is_blocked_by(hero + board))
+            .collect::<Vec<_>>();
+        let (win, lose, tie) = hero.equity_vs_range(board, &all_hands);
+        let expected = villains.iter().fold((0.0, 0.0, 0.0), |sum, villain| {
+            let equity = equity_vs(&hero, villain, &board);
+            (sum.0 + equity.0, sum.1 + equity.1, sum.2 + equity.2)
+        });
+        let n = villains.len() as f64;
+        assert!((win - expected.0 / n).abs() < 1e-9);
+        assert!((lose - expected.1 / n).abs() < 1e-9);
+        assert!((tie - expected.2 / n).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_equity_vs() {
+        let (win, lose, tie) = equity_vs_str("AsAh", "KcKd", "");
+        assert!(0.81 < win && win < 0.83);
+        assert!(0.17 < lose && lose < 0.19);
+        assert!((win + lose + tie - 1.0).abs() < 1e-9);
+
+        let (win, lose, tie) = equity_vs_str("AsAh", "KcKd", "Kh7s2c");
+        assert!(win < 0.1 && lose > 0.9);
+        assert!((win + lose + tie - 1.0).abs() < 1e-9);
+
+        // flush draw vs. top pair on the turn: 9 outs out of 44 cards
+        let (win, _, _) = equity_vs_str("8s7s", "KhQd", "Ks5s2d3c");
+        assert!((win - 9.0 / 44.0).abs() < 1e-9);
+
+        for (hero, villain, board) in &[
+            ("AsAh", "KcKd", "Kh7s2c3d4h"),
+            ("AsKh", "AdKc", "9h7s2c3d4h"),
+            ("8s7s", "KhQd", "Ks5s2d3c9s"),
+        ] {
+            let hero_rank = (hero.parse::<Hand>().unwrap() + board.parse().unwrap()).evaluate();
+            let villain_rank =
+                (villain.parse::<Hand>().unwrap() + board.parse().unwrap()).evaluate();
+            let expected = if hero_rank > villain_rank {
+                (1.0, 0.0, 0.0)
+            } else if hero_rank < villain_rank {
+                (0.0, 1.0, 0.0)
+            } else {
+                (0.0, 0.0, 1.0)
+            };
+            assert_eq!(equity_vs_str(hero, villain, board), expected);
+        }
+    }
+}
@@ -0,0 +1,140 @@
+use crate::card::*;
+use crate::hand::*;
+use std::fmt;
+use std::ops::Deref;
+use std::str::FromStr;
+
+/// Two hole cards of Hold'em. Dereferences to `Hand`.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct HoleCards {
+    hand: Hand,
+    cards: [Card; 2],
+}
+
+impl HoleCards {
+    /// Creates new hole cards from card indices `c0` and `c1`.
+    #[inline]
+    pub fn new(c0: usize, c1: usize) -> Result<Self, HandError> {
+        let hand = Hand::new().try_add_card(c0)?.try_add_card(c1)?;
+        let cards = hand.to_card_array();
+        Ok(Self {
+            hand,
+            cards: [cards[1], cards[0]],
+        })
+    }
+
+    /// Returns the two cards of `self`, the higher card index first.
+    #[inline]
+    pub fn cards(&self) -> [Card; 2] {
+        self.cards
+    }
+
+    /// Returns the hand consisting of the same cards as `self`.
+    #[inline]
+    pub fn to_hand(&self) -> Hand {
+        self.hand
+    }
+
+    /// Returns whether the two cards share a suit.
+    #[inline]
+    pub fn is_suited(&self) -> bool {
+        self.cards[0].suit() == self.cards[1].suit()
+    }
+
+    /// Returns whether the two cards share a rank.
+    #[inline]
+    pub fn is_pair(&self) -> bool {
+        self.rank_gap() == 0
+    }
+
+    /// Returns whether the ranks of the two cards are adjacent, e.g., T-9 or A-K.
+    /// The ace is adjacent only to the king, as in `Hand::connected_cards()`.
+    #[inline]
+    pub fn is_connector(&self) -> bool {
+        self.rank_gap() == 1
+    }
+
+    /// Returns whether exactly one rank lies between the two cards, e.g., T-8 or A-Q.
+    #[inline]
+    pub fn is_one_gap(&self) -> bool {
+        self.rank_gap() == 2
+    }
+
+    #[inline]
+    fn rank_gap(&self) -> u8 {
+        self.cards[0].rank() - self.cards[1].rank()
+    }
+}
+
+impl Deref for HoleCards {
+    type Target = Hand;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.hand
+    }
+}
+
+impl fmt::Display for HoleCards {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.hand.fmt(f)
+    }
+}
+
+impl FromStr for HoleCards {
+    type Err = String;
+
+    fn from_str(hole_str: &str) -> Result<Self, Self::Err> {
+        let hand = hole_str.parse::<Hand>()?;
+        if hand.len() != 2 {
+            return Err(format!(
+                "parse failed: expected 2 hole cards, but got {}",
+                hand.len()
+            ));
+        }
+        let cards = hand.to_card_array();
+        Self::new(cards[0].index(), cards[1].index()).map_err(|e| e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::convert::TryFrom;
+
+    fn hole(hole_str: &str) -> HoleCards {
+        hole_str.parse().unwrap()
+    }
+
+    #[test]
+    fn test_new() {
+        let hole_cards = HoleCards::new(48, 44).unwrap();
+        assert_eq!(hole_cards.to_hand(), "AsKs".parse().unwrap());
+        assert_eq!(hole_cards, HoleCards::new(44, 48).unwrap());
+        assert_eq!(hole_cards.cards()[0].index(), 48);
+        assert_eq!(hole_cards.len(), 2);
+        assert_eq!(
+            HoleCards::new(48, 48),
+            Err(HandError::DuplicateCard(Card::try_from(48).unwrap()))
+        );
+        assert_eq!(HoleCards::new(0, 52), Err(HandError::InvalidCardIndex(52)));
+    }
+
+    #[test]
+    fn test_queries() {
+        assert!(hole("AsKs").is_suited() && !hole("AsKs").is_pair());
+        assert!(hole("AsAh").is_pair() && !hole("AsAh").is_suited());
+        assert!(hole("AsKh").is_connector() && hole("Th9h").is_connector());
+        assert!(!hole("As2h").is_connector() && !hole("AsAh").is_connector());
+        assert!(hole("AsQh").is_one_gap() && hole("8dTc").is_one_gap());
+        assert!(!hole("9d8c").is_one_gap() && !hole("7d4c").is_one_gap());
+        assert_eq!(hole("Kd7c").to_string(), "7c Kd");
+    }
+
+    #[test]
+    fn test_parser() {
+        assert!("As".parse::<HoleCards>().is_err());
+        assert!("AsKsQs".parse::<HoleCards>().is_err());
+        assert!("Ax".parse::<HoleCards>().is_err());
+    }
+}
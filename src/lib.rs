@@ -1,7 +1,56 @@
+#![allow(
+    clippy::identity_op,
+    clippy::needless_range_loop,
+    clippy::suspicious_arithmetic_impl,
+    clippy::suspicious_op_assign_impl
+)]
+
+#[cfg(feature = "rayon")]
+mod batch;
+mod board;
+mod card;
+mod card_set;
+mod chinese_poker;
+mod combinations;
+mod deck;
+mod description;
 mod enumerate;
 mod hand;
+mod hand_history;
 mod hand_test;
 mod heads_up;
+mod hole_cards;
+#[cfg(feature = "rand")]
+mod monte_carlo;
+mod range;
+#[cfg(feature = "serde")]
+mod serialize;
+mod short_deck;
+mod showdown;
+mod simd;
+#[cfg(test)]
+mod test_util;
+mod texture;
+mod three_card;
+#[cfg(feature = "rayon")]
+pub use batch::*;
+pub use board::*;
+pub use card::*;
+pub use card_set::*;
+pub use chinese_poker::*;
+pub use combinations::*;
+pub use deck::*;
+pub use description::*;
 pub use enumerate::*;
 pub use hand::*;
+pub use hand_history::*;
 pub use heads_up::*;
+pub use hole_cards::*;
+#[cfg(feature = "rand")]
+pub use monte_carlo::*;
+pub use range::*;
+pub use short_deck::*;
+pub use showdown::*;
+pub use simd::*;
+pub use texture::*;
+pub use three_card::*;
@@ -0,0 +1,198 @@
+use crate::card::*;
+use crate::card_set::*;
+use crate::hand::*;
+use crate::range::*;
+use crate::showdown::*;
+use assets::constants::*;
+use rand::{seq::SliceRandom, Rng};
+use std::convert::TryFrom;
+
+impl Hand {
+    /// Estimates the equity of each of `hands` (2-9 hands of 2 hole cards) by sampling
+    /// `iterations` random runouts of `board` (0-5 cards). Ties split the pot equally among the
+    /// tying hands, so the return value sums up to 1.
+    pub fn equity_monte_carlo<R: Rng + ?Sized>(
+        hands: &[Hand],
+        board: Hand,
+        iterations: u32,
+        rng: &mut R,
+    ) -> Vec<f64> {
+        assert!(2 <= hands.len() && hands.len() <= 9);
+        assert!(board.len() <= 5);
+        assert!(iterations > 0);
+        let mut dead = CardSet::from(board);
+        for hand in hands {
+            assert_eq!(hand.len(), 2);
+            let hand = CardSet::from(*hand);
+            assert!(dead.intersection(hand).is_empty());
+            dead = dead.union(hand);
+        }
+
+        let alive_cards = (0..NUMBER_OF_CARDS)
+            .map(|i| Card::try_from(i).unwrap())
+            .filter(|card| !dead.contains(*card))
+            .collect::<Vec<_>>();
+        let missing = 5 - board.len();
+        let mut equity = vec![0.0; hands.len()];
+
+        for _ in 0..iterations {
+            let runout = alive_cards
+                .choose_multiple(rng, missing)
+                .fold(board, |runout, card| runout.add_card(*card));
+            let winners = winners_among(hands, runout);
+            for i in &winners {
+                equity[*i] += 1.0 / winners.len() as f64;
+            }
+        }
+
+        for e in &mut equity {
+            *e /= iterations as f64;
+        }
+        equity
+    }
+}
+
+/// maximum number of consecutive attempts to deal conflict-free hole cards in
+/// `simulate_tournament()`
+const MAX_DEAL_ATTEMPTS: usize = 10000;
+
+/// Estimates the all-in equity of each player whose hole cards are drawn from `player_ranges`
+/// (2-9 non-empty ranges), by sampling `iterations` deals: each player receives a random
+/// combination of their range (deals with conflicting cards are redrawn) and a random 5-card board
+/// is dealt from the remaining cards. Ties split the pot equally, so the return value sums up to 1.
+/// This function panics when no conflict-free deal is found in a reasonable number of attempts,
+/// e.g., when the ranges cannot be dealt together at all.
+pub fn simulate_tournament<R: Rng + ?Sized>(
+    player_ranges: &[HandRange],
+    iterations: u32,
+    rng: &mut R,
+) -> Vec<f64> {
+    assert!(2 <= player_ranges.len() && player_ranges.len() <= 9);
+    assert!(player_ranges.iter().all(|range| !range.is_empty()));
+    assert!(iterations > 0);
+    let mut equity = vec![0.0; player_ranges.len()];
+    let mut hands = vec![Hand::new(); player_ranges.len()];
+
+    for _ in 0..iterations {
+        let dead = deal_hole_cards(player_ranges, &mut hands, rng);
+        let alive_cards = (0..NUMBER_OF_CARDS)
+            .map(|i| Card::try_from(i).unwrap())
+            .filter(|card| !dead.contains(*card))
+            .collect::<Vec<_>>();
+        let board = alive_cards
+            .choose_multiple(rng, 5)
+            .fold(Hand::new(), |board, card| board.add_card(*card));
+        let showdown = hands.iter().map(|hand| *hand + board).collect::<Vec<_>>();
+        let winners = find_winners(&showdown);
+        for i in &winners {
+            equity[*i] += 1.0 / winners.len() as f64;
+        }
+    }
+
+    for e in &mut equity {
+        *e /= iterations as f64;
+    }
+    equity
+}
+
+/// Deals one combination of each range into `hands` without card conflicts, by redrawing the whole
+/// deal on conflict, and returns the union of the dealt cards.
+fn deal_hole_cards<R: Rng + ?Sized>(
+    player_ranges: &[HandRange],
+    hands: &mut [Hand],
+    rng: &mut R,
+) -> CardSet {
+    'attempt: for _ in 0..MAX_DEAL_ATTEMPTS {
+        let mut dead = CardSet::new();
+        for (hand, range) in hands.iter_mut().zip(player_ranges) {
+            let (c0, c1) = *range.combos().choose(rng).unwrap();
+            if dead.contains(c0) || dead.contains(c1) {
+                continue 'attempt;
+            }
+            *hand = Hand::from_slice(&[c0, c1]);
+            dead.insert(c0);
+            dead.insert(c1);
+        }
+        return dead;
+    }
+    panic!("failed to deal hole cards without conflicts");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::heads_up::*;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    fn hand(hand_str: &str) -> Hand {
+        hand_str.parse().unwrap()
+    }
+
+    #[test]
+    fn test_equity_monte_carlo() {
+        let mut rng = StdRng::seed_from_u64(0);
+
+        for (hero, villain, board) in &[
+            ("AsAh", "KcKd", ""),
+            ("AsKh", "7c7d", ""),
+            ("8s7s", "KhQd", "Ks5s2d"),
+        ] {
+            let (hero, villain, board) = (hand(hero), hand(villain), hand(board));
+            let (win, lose, tie) = equity_vs(&hero, &villain, &board);
+            let equity = Hand::equity_monte_carlo(&[hero, villain], board, 100_000, &mut rng);
+            assert!((equity[0] - (win + tie / 2.0)).abs() < 0.01);
+            assert!((equity[1] - (lose + tie / 2.0)).abs() < 0.01);
+        }
+
+        // AhKc and AsKd are symmetric with respect to 7c7d
+        let hands = [hand("AhKc"), hand("AsKd"), hand("7c7d")];
+        let equity = Hand::equity_monte_carlo(&hands, Hand::new(), 100_000, &mut rng);
+        assert!((equity[0] - equity[1]).abs() < 0.01);
+        assert!((equity.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+
+        // complete board
+        let hands = [hand("AsAh"), hand("AcAd"), hand("KsKh")];
+        let equity = Hand::equity_monte_carlo(&hands, hand("2s3h7c8dTs"), 10, &mut rng);
+        assert_eq!(equity, vec![0.5, 0.5, 0.0]);
+    }
+
+    #[test]
+    fn test_simulate_tournament() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let range = |range_str: &str| range_str.parse::<HandRange>().unwrap();
+
+        // AA vs. KK is about 82% vs. 18%
+        let equity = simulate_tournament(&[range("AA"), range("KK")], 100_000, &mut rng);
+        assert!((equity[0] - 0.82).abs() < 0.01);
+        assert!((equity.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+
+        // a single combination per player matches equity_monte_carlo()
+        let equity = simulate_tournament(&[range("AsKh"), range("7c7d")], 100_000, &mut rng);
+        let (win, _, tie) = equity_vs(&hand("AsKh"), &hand("7c7d"), &Hand::new());
+        assert!((equity[0] - (win + tie / 2.0)).abs() < 0.01);
+
+        // 6-player all-in with overlapping ranges
+        let ranges = [
+            "AA,KK",
+            "AK,AQ",
+            "QQ,JJ,TT",
+            "AKs,KQs",
+            "22,33,44",
+            "T9s,98s,87s",
+        ]
+        .iter()
+        .map(|range_str| range(range_str))
+        .collect::<Vec<_>>();
+        let equity = simulate_tournament(&ranges, 10_000, &mut rng);
+        assert_eq!(equity.len(), 6);
+        assert!(equity.iter().all(|e| *e > 0.0));
+        assert!((equity.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_simulate_tournament_conflict() {
+        let ranges = ["AsAh".parse().unwrap(), "AsKd".parse().unwrap()];
+        simulate_tournament(&ranges, 1, &mut StdRng::seed_from_u64(0));
+    }
+}
@@ -0,0 +1,191 @@
+use crate::card::*;
+use crate::card_set::*;
+use crate::hand::*;
+use assets::constants::*;
+use std::str::FromStr;
+
+/// A set of two-card hole hand combinations, e.g., "AA,KK,AKs".
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct HandRange {
+    combos: Vec<(Card, Card)>,
+}
+
+impl HandRange {
+    /// Creates an empty range.
+    #[inline]
+    pub fn new() -> Self {
+        Self { combos: Vec::new() }
+    }
+
+    /// Checks whether the range is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.combos.is_empty()
+    }
+
+    /// Returns the number of combinations in `self`.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.combos.len()
+    }
+
+    /// Returns the combinations as pairs of cards, in the order they were added.
+    /// The first card of each pair has the smaller card index.
+    #[inline]
+    pub fn combos(&self) -> &[(Card, Card)] {
+        &self.combos
+    }
+
+    /// Returns an iterator over the combinations as two-card hands.
+    #[inline]
+    pub fn hands(&self) -> impl Iterator<Item = Hand> + '_ {
+        self.combos
+            .iter()
+            .map(|(c0, c1)| Hand::from_slice(&[*c0, *c1]))
+    }
+
+    /// Returns whether `self` includes the two-card `hand`.
+    pub fn contains(&self, hand: &Hand) -> bool {
+        self.hands().any(|h| h == *hand)
+    }
+
+    /// Adds the combination of `c0` and `c1` to `self` unless it is already included.
+    /// `c0` and `c1` must be distinct.
+    pub fn add_combo(&mut self, c0: Card, c1: Card) {
+        assert_ne!(c0, c1);
+        let combo = (c0.min(c1), c0.max(c1));
+        if !self.combos.contains(&combo) {
+            self.combos.push(combo);
+        }
+    }
+
+    /// Returns the range without the combinations that contain any card of `dead` (e.g., the
+    /// board cards).
+    pub fn remove_dead_cards(&self, dead: CardSet) -> Self {
+        Self {
+            combos: self
+                .combos
+                .iter()
+                .filter(|(c0, c1)| !dead.contains(*c0) && !dead.contains(*c1))
+                .copied()
+                .collect(),
+        }
+    }
+}
+
+fn parse_rank(rank_char: char) -> Result<u8, String> {
+    (0..NUMBER_OF_RANKS as u8)
+        .find(|rank| rank_to_string(*rank).starts_with(rank_char.to_ascii_uppercase()))
+        .ok_or_else(|| {
+            format!(
+                "parse failed: expected rank character, but got '{}'",
+                rank_char
+            )
+        })
+}
+
+/// Parses comma-separated hand classes: pairs ("AA"), suited hands ("AKs"), offsuit hands
+/// ("AKo"), both suited and offsuit hands ("AK"), and specific combinations ("AsKd").
+impl FromStr for HandRange {
+    type Err = String;
+
+    fn from_str(range_str: &str) -> Result<Self, Self::Err> {
+        let mut range = Self::new();
+        if range_str.trim().is_empty() {
+            return Ok(range);
+        }
+        for token in range_str.split(',').map(str::trim) {
+            let chars = token.chars().collect::<Vec<_>>();
+            if chars.len() == 4 {
+                let c0 = chars[..2].iter().collect::<String>().parse::<Card>()?;
+                let c1 = chars[2..].iter().collect::<String>().parse::<Card>()?;
+                if c0 == c1 {
+                    return Err(format!("parse failed: duplicate card in '{}'", token));
+                }
+                range.add_combo(c0, c1);
+                continue;
+            }
+            if chars.len() != 2 && chars.len() != 3 {
+                return Err(format!("parse failed: invalid hand class '{}'", token));
+            }
+            let rank0 = parse_rank(chars[0])?;
+            let rank1 = parse_rank(chars[1])?;
+            let (suited, offsuit) = match chars.get(2).map(|ch| ch.to_ascii_lowercase()) {
+                None => (true, true),
+                Some('s') => (true, false),
+                Some('o') => (false, true),
+                Some(ch) => {
+                    return Err(format!(
+                        "parse failed: expected 's' or 'o', but got '{}'",
+                        ch
+                    ))
+                }
+            };
+            if rank0 == rank1 && chars.len() == 3 {
+                return Err(format!("parse failed: invalid hand class '{}'", token));
+            }
+            for suit0 in 0..4 {
+                for suit1 in 0..4 {
+                    let is_valid = if rank0 == rank1 {
+                        suit0 < suit1
+                    } else if suit0 == suit1 {
+                        suited
+                    } else {
+                        offsuit
+                    };
+                    if is_valid {
+                        let c0 = Card::new(rank0, suit0).unwrap();
+                        let c1 = Card::new(rank1, suit1).unwrap();
+                        range.add_combo(c0, c1);
+                    }
+                }
+            }
+        }
+        Ok(range)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn range(range_str: &str) -> HandRange {
+        range_str.parse().unwrap()
+    }
+
+    #[test]
+    fn test_parser() {
+        assert_eq!(range("AA").len(), 6);
+        assert_eq!(range("AKs").len(), 4);
+        assert_eq!(range("AKo").len(), 12);
+        assert_eq!(range("AK").len(), 16);
+        assert_eq!(range("KA").len(), 16);
+        assert_eq!(range("AsKd").len(), 1);
+        assert_eq!(range("AA,KK,AKs").len(), 16);
+        assert_eq!(range(" AA , kk,aks ").len(), 16);
+        assert_eq!(range("AK,AKs,AsKs").len(), 16);
+        assert!(range("").is_empty());
+
+        let suited = range("AKs");
+        for hand in suited.hands() {
+            assert_eq!(hand.len(), 2);
+            assert!(hand.contains_rank(12) && hand.contains_rank(11));
+        }
+        assert!(suited.contains(&"AhKh".parse().unwrap()));
+        assert!(!suited.contains(&"AhKd".parse().unwrap()));
+        assert!(range("AA").contains(&"AcAd".parse().unwrap()));
+
+        for range_str in &["A", "AKx", "AAs", "1K", "AKsK", "AsAs", "AA,", "AsKx"] {
+            assert!(range_str.parse::<HandRange>().is_err());
+        }
+    }
+
+    #[test]
+    fn test_remove_dead_cards() {
+        let board = "AsKh7d".parse::<Hand>().unwrap();
+        let range = range("AA,KK,AKs,72o").remove_dead_cards(CardSet::from(board));
+        assert_eq!(range.len(), 3 + 3 + 2 + 9);
+        assert!(range.hands().all(|hand| !hand.is_blocked_by(board)));
+        assert_eq!(range.remove_dead_cards(CardSet::new()), range);
+    }
+}
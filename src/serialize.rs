@@ -0,0 +1,93 @@
+use crate::hand::*;
+use serde::de::{self, Deserialize, Deserializer};
+use serde::ser::{Serialize, Serializer};
+
+const CATEGORY_NAMES: [&str; 9] = [
+    "high_card",
+    "one_pair",
+    "two_pair",
+    "three_of_a_kind",
+    "straight",
+    "flush",
+    "full_house",
+    "four_of_a_kind",
+    "straight_flush",
+];
+
+const CATEGORIES: [HandCategory; 9] = [
+    HandCategory::HighCard,
+    HandCategory::OnePair,
+    HandCategory::TwoPair,
+    HandCategory::ThreeOfAKind,
+    HandCategory::Straight,
+    HandCategory::Flush,
+    HandCategory::FullHouse,
+    HandCategory::FourOfAKind,
+    HandCategory::StraightFlush,
+];
+
+/// Serializes the hand as the string produced by `Display`.
+impl Serialize for Hand {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+/// Deserializes the hand from a string accepted by `FromStr`.
+impl<'de> Deserialize<'de> for Hand {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let hand_str = String::deserialize(deserializer)?;
+        hand_str.parse::<Hand>().map_err(de::Error::custom)
+    }
+}
+
+/// Serializes the hand category as its snake_case name (e.g., "straight_flush").
+impl Serialize for HandCategory {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(CATEGORY_NAMES[*self as usize])
+    }
+}
+
+impl<'de> Deserialize<'de> for HandCategory {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        match CATEGORY_NAMES.iter().position(|n| *n == name) {
+            Some(i) => Ok(CATEGORIES[i]),
+            None => Err(de::Error::unknown_variant(&name, &CATEGORY_NAMES)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hand_serde() {
+        for hand_str in &["", "As", "AhKdQc", "AsKsQsJsTs7d5s"] {
+            let hand = hand_str.parse::<Hand>().unwrap();
+            let json = serde_json::to_string(&hand).unwrap();
+            assert_eq!(json, format!("\"{}\"", hand));
+            assert_eq!(serde_json::from_str::<Hand>(&json).unwrap(), hand);
+        }
+        assert!(serde_json::from_str::<Hand>("\"Ax\"").is_err());
+        assert!(serde_json::from_str::<Hand>("42").is_err());
+    }
+
+    #[test]
+    fn test_hand_category_serde() {
+        for category in &CATEGORIES {
+            let json = serde_json::to_string(category).unwrap();
+            assert_eq!(
+                serde_json::from_str::<HandCategory>(&json).unwrap(),
+                *category
+            );
+        }
+        assert_eq!(
+            serde_json::to_string(&HandCategory::StraightFlush).unwrap(),
+            "\"straight_flush\""
+        );
+        let err = serde_json::from_str::<HandCategory>("\"royal_flush\"").unwrap_err();
+        assert!(err.to_string().contains("unknown variant `royal_flush`"));
+    }
+}
@@ -0,0 +1,265 @@
+use crate::card::*;
+use crate::combinations::*;
+use crate::hand::*;
+use std::fmt;
+use std::str::FromStr;
+
+/// Hand categories of Short Deck Hold'em (6+ poker), in ascending order of strength.
+/// Unlike the standard ranking, three of a kind beats a straight and a flush beats a full house.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum ShortDeckHandCategory {
+    HighCard = 0,
+    OnePair = 1,
+    TwoPair = 2,
+    Straight = 3,
+    ThreeOfAKind = 4,
+    FullHouse = 5,
+    Flush = 6,
+    FourOfAKind = 7,
+    StraightFlush = 8,
+}
+
+/// Returns the short deck hand category from hand rank computed by `ShortDeckHand::evaluate()`.
+#[inline]
+pub fn get_short_deck_hand_category(hand_rank: u16) -> ShortDeckHandCategory {
+    match hand_rank >> 12 {
+        0 => ShortDeckHandCategory::HighCard,
+        1 => ShortDeckHandCategory::OnePair,
+        2 => ShortDeckHandCategory::TwoPair,
+        3 => ShortDeckHandCategory::Straight,
+        4 => ShortDeckHandCategory::ThreeOfAKind,
+        5 => ShortDeckHandCategory::FullHouse,
+        6 => ShortDeckHandCategory::Flush,
+        7 => ShortDeckHandCategory::FourOfAKind,
+        8 => ShortDeckHandCategory::StraightFlush,
+        _ => unreachable!(),
+    }
+}
+
+/// lowest rank of the short deck (six)
+const MIN_RANK: u8 = 4;
+
+/// rank set of A-6-7-8-9, the lowest straight in the short deck
+const LOW_STRAIGHT: u64 = 0b1_0000_1111_0000;
+
+/// A hand of Short Deck Hold'em, which is played with the 36 cards from sixes to aces
+/// (card indices \[16, 51\]).
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct ShortDeckHand {
+    hand: Hand,
+}
+
+impl ShortDeckHand {
+    /// Creates a new empty hand.
+    #[inline]
+    pub fn new() -> Self {
+        Self { hand: Hand::new() }
+    }
+
+    /// Creates a new hand structure consists of `cards`.
+    #[inline]
+    pub fn from_slice(cards: &[Card]) -> Self {
+        let mut hand = Self::new();
+        for card in cards {
+            hand = hand.add_card(*card);
+        }
+        hand
+    }
+
+    /// Checks whether the hand is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.hand.is_empty()
+    }
+
+    /// Returns current number of cards in `self`.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.hand.len()
+    }
+
+    /// Returns whether `card` is included in `self`.
+    #[inline]
+    pub fn contains(&self, card: Card) -> bool {
+        self.hand.contains(card)
+    }
+
+    /// Returns the standard hand consisting of the same cards as `self`.
+    #[inline]
+    pub fn to_hand(&self) -> Hand {
+        self.hand
+    }
+
+    /// Returns a new hand struct where `card` is added to `self`.
+    /// `card` must not be already included in `self`.
+    /// This function panics when `card` is a deuce, three, four or five.
+    #[inline]
+    pub fn add_card(&self, card: Card) -> Self {
+        assert!(card.rank() >= MIN_RANK);
+        Self {
+            hand: self.hand.add_card(card),
+        }
+    }
+
+    /// Returns a new hand struct where `card` is removed from `self`.
+    /// `card` must be included in `self`.
+    #[inline]
+    pub fn remove_card(&self, card: Card) -> Self {
+        Self {
+            hand: self.hand.remove_card(card),
+        }
+    }
+
+    /// Returns hand strength in 16-bit integer under the short deck ranking.
+    /// The upper 4 bits hold the `ShortDeckHandCategory`, and A-6-7-8-9 is the lowest straight.
+    /// This function panics when `self.len() < 5 || self.len() > 7`.
+    pub fn evaluate(&self) -> u16 {
+        assert!(5 <= self.len() && self.len() <= 7);
+        HandCombinations::new(self.hand, 5)
+            .map(|five| evaluate_short_deck_five(&five))
+            .max()
+            .unwrap()
+    }
+}
+
+/// Evaluates 5-card `hand` with the standard lookup tables and remaps its category.
+fn evaluate_short_deck_five(hand: &Hand) -> u16 {
+    let rank = hand.evaluate();
+    let rankset = hand.rankset();
+    // A-6-7-8-9 takes the place of the 9-high straight, which is impossible in the short deck
+    let (category, index) = match get_hand_category(rank) {
+        HandCategory::HighCard if rankset == LOW_STRAIGHT => (ShortDeckHandCategory::Straight, 4),
+        HandCategory::Flush if rankset == LOW_STRAIGHT => (ShortDeckHandCategory::StraightFlush, 4),
+        category => {
+            let category = match category {
+                HandCategory::HighCard => ShortDeckHandCategory::HighCard,
+                HandCategory::OnePair => ShortDeckHandCategory::OnePair,
+                HandCategory::TwoPair => ShortDeckHandCategory::TwoPair,
+                HandCategory::ThreeOfAKind => ShortDeckHandCategory::ThreeOfAKind,
+                HandCategory::Straight => ShortDeckHandCategory::Straight,
+                HandCategory::Flush => ShortDeckHandCategory::Flush,
+                HandCategory::FullHouse => ShortDeckHandCategory::FullHouse,
+                HandCategory::FourOfAKind => ShortDeckHandCategory::FourOfAKind,
+                HandCategory::StraightFlush => ShortDeckHandCategory::StraightFlush,
+            };
+            (category, rank & 0x0fff)
+        }
+    };
+    ((category as u16) << 12) | index
+}
+
+impl fmt::Display for ShortDeckHand {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.hand.fmt(f)
+    }
+}
+
+impl FromStr for ShortDeckHand {
+    type Err = String;
+
+    fn from_str(hand_str: &str) -> Result<Self, Self::Err> {
+        let hand = hand_str.parse::<Hand>()?;
+        if let Some(card) = hand.cards().find(|card| card.rank() < MIN_RANK) {
+            return Err(format!("parse failed: '{}' is not in the short deck", card));
+        }
+        Ok(Self { hand })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assets::constants::*;
+    use std::collections::HashSet;
+    use std::convert::TryFrom;
+
+    fn evaluate(hand_str: &str) -> u16 {
+        hand_str.parse::<ShortDeckHand>().unwrap().evaluate()
+    }
+
+    #[test]
+    fn test_all_5card_combinations() {
+        let cards = (16..NUMBER_OF_CARDS)
+            .map(|i| Card::try_from(i).unwrap())
+            .collect::<Vec<_>>();
+        let len = cards.len();
+        let mut rankset = HashSet::new();
+        let mut counter = vec![0; ShortDeckHandCategory::StraightFlush as usize + 1];
+
+        for i in 0..(len - 4) {
+            let hand = ShortDeckHand::new().add_card(cards[i]);
+            for j in (i + 1)..(len - 3) {
+                let hand = hand.add_card(cards[j]);
+                for k in (j + 1)..(len - 2) {
+                    let hand = hand.add_card(cards[k]);
+                    for m in (k + 1)..(len - 1) {
+                        let hand = hand.add_card(cards[m]);
+                        for n in (m + 1)..len {
+                            let hand = hand.add_card(cards[n]);
+                            let rank = hand.evaluate();
+                            rankset.insert(rank);
+                            counter[get_short_deck_hand_category(rank) as usize] += 1;
+                        }
+                    }
+                }
+            }
+        }
+
+        assert_eq!(counter.iter().sum::<usize>(), 376992);
+        assert_eq!(counter[ShortDeckHandCategory::StraightFlush as usize], 24);
+        assert_eq!(counter[ShortDeckHandCategory::FourOfAKind as usize], 288);
+        assert_eq!(counter[ShortDeckHandCategory::Flush as usize], 480);
+        assert_eq!(counter[ShortDeckHandCategory::FullHouse as usize], 1728);
+        assert_eq!(counter[ShortDeckHandCategory::ThreeOfAKind as usize], 16128);
+        assert_eq!(counter[ShortDeckHandCategory::Straight as usize], 6120);
+        assert_eq!(counter[ShortDeckHandCategory::TwoPair as usize], 36288);
+        assert_eq!(counter[ShortDeckHandCategory::OnePair as usize], 193536);
+        assert_eq!(counter[ShortDeckHandCategory::HighCard as usize], 122400);
+        assert_eq!(rankset.len(), 1404);
+    }
+
+    #[test]
+    fn test_ordering() {
+        assert!(ShortDeckHandCategory::FullHouse < ShortDeckHandCategory::Flush);
+        assert!(ShortDeckHandCategory::Straight < ShortDeckHandCategory::ThreeOfAKind);
+        assert!(evaluate("AsKsQsJs9s") > evaluate("AhAdAcKsKh"));
+        assert!(evaluate("6s6h6cAdKs") > evaluate("AsKhQcJdTs"));
+
+        // A-6-7-8-9 is the lowest straight
+        let low = evaluate("As6h7c8d9s");
+        assert_eq!(
+            get_short_deck_hand_category(low),
+            ShortDeckHandCategory::Straight
+        );
+        assert!(low < evaluate("6s7h8c9dTs"));
+        assert!(low > evaluate("AsKhQcJd9s"));
+        let low_flush = evaluate("As6s7s8s9s");
+        assert_eq!(
+            get_short_deck_hand_category(low_flush),
+            ShortDeckHandCategory::StraightFlush
+        );
+        assert!(low_flush < evaluate("6s7s8s9sTs"));
+
+        // the best 5 cards differ from the standard ranking
+        assert_eq!(evaluate("8s8h8c9dTsJhQc"), evaluate("8s8h8cQcJh"));
+        assert_eq!(evaluate("AsAh6c7d8s9hKc"), evaluate("As6c7d8s9h"));
+    }
+
+    #[test]
+    fn test_parser() {
+        assert!("AsKs".parse::<ShortDeckHand>().is_ok());
+        assert_eq!(
+            "As5s".parse::<ShortDeckHand>(),
+            Err("parse failed: '5s' is not in the short deck".into())
+        );
+        let hand = "Qc Kd Ah".parse::<ShortDeckHand>().unwrap();
+        assert_eq!(hand.to_string(), "Qc Kd Ah");
+        assert_eq!(hand.to_hand(), "AhKdQc".parse::<Hand>().unwrap());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_add_invalid_card() {
+        ShortDeckHand::new().add_card(Card::new(3, 0).unwrap());
+    }
+}
@@ -0,0 +1,166 @@
+use crate::combinations::*;
+use crate::hand::*;
+use std::cmp::{Ordering, Reverse};
+
+/// Returns the indices of `hands` sorted from the strongest hand to the weakest hand.
+/// Tied hands are adjacent and keep their original order. Every hand must consist of 5-7 cards.
+pub fn compare_hands(hands: &[Hand]) -> Vec<usize> {
+    let ranks = hands.iter().map(Hand::evaluate).collect::<Vec<_>>();
+    let mut indices = (0..hands.len()).collect::<Vec<_>>();
+    indices.sort_by_key(|i| Reverse(ranks[*i]));
+    indices
+}
+
+/// Returns the indices of `hands` that share the strongest rank, i.e., the winners of the pot, in
+/// ascending order. Returns an empty vector when `hands` is empty.
+/// Every hand must consist of 5-7 cards.
+pub fn find_winners(hands: &[Hand]) -> Vec<usize> {
+    let ranks = hands.iter().map(Hand::evaluate).collect::<Vec<_>>();
+    let best = ranks.iter().max();
+    (0..hands.len())
+        .filter(|i| Some(&ranks[*i]) == best)
+        .collect()
+}
+
+/// Returns the index of the winner of a showdown among players holding `hole_cards` with the
+/// shared `board`, where every hole hand plus `board` must consist of 5-7 cards. Ties are resolved
+/// to the smallest index; use `winners_among()` to get all tied players.
+/// This function panics when `hole_cards` is empty.
+pub fn best_hand_among(hole_cards: &[Hand], board: Hand) -> usize {
+    winners_among(hole_cards, board)[0]
+}
+
+/// Returns the indices of the players holding `hole_cards` who win the showdown with the shared
+/// `board`, in ascending order. This is `find_winners()` of the hole hands combined with `board`.
+pub fn winners_among(hole_cards: &[Hand], board: Hand) -> Vec<usize> {
+    let showdown = hole_cards
+        .iter()
+        .map(|hole| *hole + board)
+        .collect::<Vec<_>>();
+    find_winners(&showdown)
+}
+
+/// Suit order used for tiebreaking, from the weakest to the strongest: clubs, diamonds, hearts
+/// and spades (indexed by the suit of `Card`).
+const TIEBREAK_SUIT_ORDER: [u8; 4] = [3, 2, 0, 1];
+
+impl Hand {
+    /// Compares `self` and `other` (5-7 cards each) by `evaluate()`, and breaks exact ties by
+    /// suits: the best 5 cards of each hand are compared card by card from the highest rank, where
+    /// spades > hearts > diamonds > clubs. Only the hands whose best 5 cards are identical compare
+    /// equal.
+    ///
+    /// This is intended only for tournament rules that require a deterministic winner, e.g., for
+    /// awarding an odd chip or a seat; suits never matter under the standard showdown rules.
+    pub fn tiebreak_rank(&self, other: &Hand) -> Ordering {
+        self.evaluate()
+            .cmp(&other.evaluate())
+            .then_with(|| tiebreak_key(self).cmp(&tiebreak_key(other)))
+    }
+}
+
+/// Returns the best 5 cards of `hand` as (rank, suit order) pairs in descending order. If several
+/// 5-card subsets achieve the best rank, the one with the strongest suits is chosen.
+fn tiebreak_key(hand: &Hand) -> [(u8, u8); 5] {
+    let best = hand.evaluate();
+    let mut key = [(0, 0); 5];
+    for five in HandCombinations::new(*hand, 5).filter(|five| five.evaluate() == best) {
+        let mut candidate = [(0, 0); 5];
+        for (c, card) in candidate.iter_mut().zip(five.cards()) {
+            *c = (card.rank(), TIEBREAK_SUIT_ORDER[card.suit() as usize]);
+        }
+        candidate.sort_unstable_by(|a, b| b.cmp(a));
+        key = key.max(candidate);
+    }
+    key
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hands(hand_strs: &[&str]) -> Vec<Hand> {
+        hand_strs.iter().map(|s| s.parse().unwrap()).collect()
+    }
+
+    #[test]
+    fn test_compare_hands() {
+        let board = "Ks9h7d4c2s".parse::<Hand>().unwrap();
+        let showdown = hands(&["AsQd", "KhKd", "9s9d", "Ac3c"])
+            .into_iter()
+            .map(|hole| hole + board)
+            .collect::<Vec<_>>();
+        assert_eq!(compare_hands(&showdown), vec![1, 2, 0, 3]);
+
+        // ties are adjacent, in the original order
+        let showdown = hands(&["AsAhKdQc2s", "AcAdKsQh2d", "AcAdKsQh3d", "AcAdKsJh2d"]);
+        assert_eq!(compare_hands(&showdown), vec![2, 0, 1, 3]);
+        assert_eq!(compare_hands(&showdown[..2]), vec![0, 1]);
+        assert!(compare_hands(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_find_winners() {
+        let board = "AsKsQhJd2c".parse::<Hand>().unwrap();
+        let showdown = hands(&["Tc3d", "Th4d", "TdTs", "AhAd"])
+            .into_iter()
+            .map(|hole| hole + board)
+            .collect::<Vec<_>>();
+        assert_eq!(find_winners(&showdown), vec![0, 1, 2]);
+        assert_eq!(find_winners(&showdown[3..]), vec![0]);
+
+        let showdown = hands(&["AsAhKdQc2s", "AcAdKsQh3d", "AcAdKsJh2d"]);
+        assert_eq!(find_winners(&showdown), vec![1]);
+        assert!(find_winners(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_best_hand_among() {
+        let board = "Ks9h7d4c2s".parse::<Hand>().unwrap();
+        let holes = hands(&["AsQd", "8h3c", "KhKd", "9s9d"]);
+        assert_eq!(best_hand_among(&holes, board), 2);
+        assert_eq!(best_hand_among(&holes[..2], board), 0);
+        assert_eq!(winners_among(&holes, board), vec![2]);
+
+        // ties go to the smallest index
+        let holes = hands(&["8h3c", "AsQd", "AhQc", "KhKd"]);
+        let board = "AdQsJcJhJs".parse::<Hand>().unwrap();
+        assert_eq!(best_hand_among(&holes, board), 1);
+        assert_eq!(winners_among(&holes, board), vec![1, 2]);
+        assert!(winners_among(&[], board).is_empty());
+    }
+
+    #[test]
+    fn test_tiebreak_rank() {
+        let hand = |hand_str: &str| hand_str.parse::<Hand>().unwrap();
+        let board = hand("Ks9h7d4c2s");
+
+        // different hand ranks are compared as usual
+        let (aa, kq) = (hand("AcAd") + board, hand("AsQs") + board);
+        assert_eq!(aa.tiebreak_rank(&kq), Ordering::Greater);
+        assert_eq!(kq.tiebreak_rank(&aa), Ordering::Less);
+
+        // the same 5-card rank is broken by the suit of the top card
+        let (ace_spades, ace_hearts) = (hand("AsQd") + board, hand("AhQc") + board);
+        assert_eq!(ace_spades.evaluate(), ace_hearts.evaluate());
+        assert_eq!(ace_spades.tiebreak_rank(&ace_hearts), Ordering::Greater);
+        assert_eq!(ace_hearts.tiebreak_rank(&ace_spades), Ordering::Less);
+        let (ace_diamonds, ace_clubs) = (hand("AdQh") + board, hand("AcQh") + board);
+        assert_eq!(ace_diamonds.tiebreak_rank(&ace_clubs), Ordering::Greater);
+
+        // then by the following cards
+        let (queen_hearts, queen_clubs) = (hand("AsQh") + board, hand("AsQc") + board);
+        assert_eq!(queen_hearts.tiebreak_rank(&queen_clubs), Ordering::Greater);
+
+        // cards outside of the best 5 cards do not matter
+        let board = hand("AsKsQhJd9c");
+        assert_eq!(
+            (hand("3s2h") + board).tiebreak_rank(&(hand("3c2d") + board)),
+            Ordering::Equal
+        );
+        assert_eq!(
+            (hand("Ts2h") + board).tiebreak_rank(&(hand("Th3d") + board)),
+            Ordering::Greater
+        );
+    }
+}
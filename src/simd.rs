@@ -0,0 +1,140 @@
+use crate::hand::*;
+
+/// Evaluates 8 hands at once and returns their hand strengths in the same order.
+///
+/// On x86-64 processors with AVX2, the lookups of non-flush hands are vectorized with gather
+/// instructions and flush hands fall back to `Hand::evaluate()`. On other processors, every hand
+/// is evaluated with `Hand::evaluate()`. In both cases, the result is identical to 8 sequential
+/// calls of `Hand::evaluate()`, and every hand must satisfy the same conditions.
+pub fn evaluate_simd_x8(hands: &[Hand; 8]) -> [u16; 8] {
+    #[cfg(target_arch = "x86_64")]
+    {
+        if is_x86_feature_detected!("avx2") {
+            return unsafe { avx2::evaluate_x8(hands) };
+        }
+    }
+    let mut result = [0; 8];
+    for (rank, hand) in result.iter_mut().zip(hands) {
+        *rank = hand.evaluate();
+    }
+    result
+}
+
+#[cfg(target_arch = "x86_64")]
+mod avx2 {
+    use crate::hand::*;
+    use assets::constants::*;
+    use assets::lookup::LOOKUP;
+    use assets::offsets::OFFSETS;
+    use std::arch::x86_64::*;
+
+    /// # Safety
+    /// The processor must support AVX2.
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn evaluate_x8(hands: &[Hand; 8]) -> [u16; 8] {
+        let offsets: &'static [i32; 12500] = &OFFSETS;
+        let lookup: &'static [u16; 73775] = &LOOKUP;
+
+        let mut rank_keys = [0; 8];
+        let mut suit_keys = [0; 8];
+        for i in 0..8 {
+            let key = hands[i].get_key();
+            rank_keys[i] = key as u32 as i32;
+            suit_keys[i] = (key >> 32) as i32;
+        }
+        let rank_key = _mm256_loadu_si256(rank_keys.as_ptr() as *const __m256i);
+        let suit_key = _mm256_loadu_si256(suit_keys.as_ptr() as *const __m256i);
+
+        let offset_index = _mm256_srli_epi32::<{ OFFSET_SHIFT as i32 }>(rank_key);
+        let offset = _mm256_i32gather_epi32::<4>(offsets.as_ptr(), offset_index);
+        let hash_key = _mm256_add_epi32(rank_key, offset);
+
+        // flush hands are evaluated by the scalar path, and so is the last entry of `LOOKUP`
+        // because a 32-bit gather of it would read past the end of the table
+        let flush_mask = _mm256_set1_epi32((FLUSH_MASK >> 32) as i32);
+        let is_non_flush = _mm256_cmpeq_epi32(
+            _mm256_and_si256(suit_key, flush_mask),
+            _mm256_setzero_si256(),
+        );
+        let is_last = _mm256_cmpeq_epi32(hash_key, _mm256_set1_epi32(lookup.len() as i32 - 1));
+        let lane_mask = _mm256_andnot_si256(is_last, is_non_flush);
+
+        let gathered = _mm256_mask_i32gather_epi32::<2>(
+            _mm256_setzero_si256(),
+            lookup.as_ptr() as *const i32,
+            hash_key,
+            lane_mask,
+        );
+        let packed = _mm256_packus_epi32(
+            _mm256_and_si256(gathered, _mm256_set1_epi32(0xffff)),
+            _mm256_setzero_si256(),
+        );
+        let packed = _mm256_permute4x64_epi64::<0b1000>(packed);
+        let mut result = [0; 8];
+        _mm_storeu_si128(
+            result.as_mut_ptr() as *mut __m128i,
+            _mm256_castsi256_si128(packed),
+        );
+
+        let mut scalar_lanes = !_mm256_movemask_ps(_mm256_castsi256_ps(lane_mask)) & 0xff;
+        while scalar_lanes != 0 {
+            let i = scalar_lanes.trailing_zeros() as usize;
+            result[i] = hands[i].evaluate();
+            scalar_lanes &= scalar_lanes - 1;
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card::*;
+    use crate::test_util::*;
+    use assets::constants::*;
+    use std::convert::TryFrom;
+
+    fn card(index: usize) -> Card {
+        Card::try_from(index).unwrap()
+    }
+
+    fn check(hands: &[Hand; 8]) {
+        let expected = hands.map(|hand| hand.evaluate());
+        assert_eq!(evaluate_simd_x8(hands), expected);
+    }
+
+    #[test]
+    fn test_evaluate_simd_x8() {
+        let mut hands = Vec::new();
+        for i in 0..(NUMBER_OF_CARDS - 4) {
+            let hand = Hand::new().add_card(card(i));
+            for j in (i + 1)..(NUMBER_OF_CARDS - 3) {
+                let hand = hand.add_card(card(j));
+                for k in (j + 1)..(NUMBER_OF_CARDS - 2) {
+                    let hand = hand.add_card(card(k));
+                    for m in (k + 1)..(NUMBER_OF_CARDS - 1) {
+                        let hand = hand.add_card(card(m));
+                        for n in (m + 1)..NUMBER_OF_CARDS {
+                            hands.push(hand.add_card(card(n)));
+                        }
+                    }
+                }
+            }
+        }
+        for chunk in hands.chunks_exact(8) {
+            check(&[
+                chunk[0], chunk[1], chunk[2], chunk[3], chunk[4], chunk[5], chunk[6], chunk[7],
+            ]);
+        }
+
+        // random 7-card hands
+        let mut rng = Xorshift64::new(0x2545_f491_4f6c_dd1d);
+        for _ in 0..10000 {
+            let mut eight = [Hand::new(); 8];
+            for hand in &mut eight {
+                *hand = rng.deal_hand(7);
+            }
+            check(&eight);
+        }
+    }
+}
@@ -0,0 +1,36 @@
+use crate::card::*;
+use crate::hand::*;
+use assets::constants::*;
+use std::convert::TryFrom;
+
+/// xorshift64 generator for dealing reproducible random cards in tests.
+pub(crate) struct Xorshift64 {
+    state: u64,
+}
+
+impl Xorshift64 {
+    /// Creates a new generator from nonzero `seed`.
+    pub(crate) fn new(seed: u64) -> Self {
+        assert_ne!(seed, 0);
+        Self { state: seed }
+    }
+
+    /// Deals `n` distinct random cards from a full deck.
+    pub(crate) fn deal(&mut self, n: usize) -> Vec<Card> {
+        let mut deck = (0..NUMBER_OF_CARDS).collect::<Vec<_>>();
+        (0..n)
+            .map(|_| {
+                self.state ^= self.state << 13;
+                self.state ^= self.state >> 7;
+                self.state ^= self.state << 17;
+                let index = deck.swap_remove(self.state as usize % deck.len());
+                Card::try_from(index).unwrap()
+            })
+            .collect()
+    }
+
+    /// Deals a random hand of `n` cards from a full deck.
+    pub(crate) fn deal_hand(&mut self, n: usize) -> Hand {
+        Hand::from_slice(&self.deal(n))
+    }
+}
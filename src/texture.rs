@@ -0,0 +1,230 @@
+use crate::hand::*;
+
+/// Texture of a board, computed by `Hand::texture()`.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct BoardTexture {
+    /// at least two cards share a rank
+    pub paired: bool,
+    /// all cards are of the same suit
+    pub monotone: bool,
+    /// cards are of exactly two suits
+    pub two_tone: bool,
+    /// no two cards share a suit
+    pub rainbow: bool,
+    /// some two hole cards make a straight or an open-ended straight draw with the board
+    pub has_straight_draw: bool,
+    /// at least two cards share a suit, so a player may hold a flush draw
+    pub has_flush_draw: bool,
+    /// how close the ranks are, from 0 (no two distinct ranks fit in a straight) to 10 (five ranks
+    /// of a straight): twice the maximum number of distinct ranks within a straight, minus the
+    /// number of gaps between them; e.g., 6 for A-K-Q or 9-8-7, 4 for K-J-9, 3 for K-J-2, and 0
+    /// for K-7-2
+    pub connectivity_score: u8,
+    /// a player may hold a flush draw, or at least two different pairs of hole card ranks make a
+    /// straight; otherwise the board is dry
+    pub wet: bool,
+}
+
+impl Hand {
+    /// Returns the texture of the board `self` (3-5 cards).
+    pub fn texture(&self) -> BoardTexture {
+        assert!(3 <= self.len() && self.len() <= 5);
+        let max_suit_count = *self.suit_distribution().iter().max().unwrap();
+        let rankset = self.rankset();
+
+        let connectivity_score = (0..10)
+            .map(|low| {
+                let window = (with_low_ace(rankset) >> low) & 0x1f;
+                let count = window.count_ones();
+                if count < 2 {
+                    return 0;
+                }
+                let span = 64 - window.leading_zeros() - window.trailing_zeros();
+                2 * count - (span - count)
+            })
+            .max()
+            .unwrap();
+
+        // pairs of hole card ranks making a straight, and whether any makes an open-ended draw
+        let mut straight_combos = 0;
+        let mut has_straight_draw = false;
+        for r0 in 0..13 {
+            for r1 in (r0 + 1)..13 {
+                let r = with_low_ace(rankset | (1 << r0) | (1 << r1));
+                let runs = r & (r >> 1) & (r >> 2) & (r >> 3);
+                if runs & (runs >> 1) != 0 {
+                    straight_combos += 1;
+                    has_straight_draw = true;
+                } else if runs & 0b11_1111_1110 != 0 {
+                    // four consecutive ranks with missing ranks at both ends
+                    has_straight_draw = true;
+                }
+            }
+        }
+
+        BoardTexture {
+            paired: (0..13).any(|rank| self.count_rank(rank) >= 2),
+            monotone: self.is_monotone(),
+            two_tone: self.is_two_tone(),
+            rainbow: self.is_rainbow(),
+            has_straight_draw,
+            has_flush_draw: max_suit_count >= 2,
+            connectivity_score: connectivity_score as u8,
+            wet: max_suit_count >= 2 || straight_combos >= 2,
+        }
+    }
+}
+
+/// Draws of a hand, computed by `Hand::count_draws()`.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct DrawCount {
+    /// number of suits with exactly 4 cards
+    pub flush_draws: u8,
+    /// number of runs of 4 consecutive ranks that a card of either adjacent rank completes
+    pub oesd_draws: u8,
+    /// number of straight-completing ranks not counted by `oesd_draws` (inside draws, and
+    /// one-ended draws such as A-2-3-4 or J-Q-K-A)
+    pub gutshot_draws: u8,
+    /// number of suits with exactly 3 cards (only on the flop)
+    pub backdoor_flush_draws: u8,
+    /// number of straights of which exactly 3 ranks are present (only on the flop)
+    pub backdoor_straight_draws: u8,
+}
+
+impl Hand {
+    /// Returns the draws of `self`, which consists of the hole cards and `board_size` (3-5) board
+    /// cards. Draws need cards to come, so the counts are all zero when `board_size == 5`, and
+    /// backdoor draws are counted only when `board_size == 3`. Straight (flush) draws are not
+    /// counted when `self` already contains a straight (flush).
+    pub fn count_draws(&self, board_size: usize) -> DrawCount {
+        assert!((3..=5).contains(&board_size) && board_size <= self.len());
+        let mut draws = DrawCount::default();
+        if board_size == 5 {
+            return draws;
+        }
+
+        if !self.has_flush() {
+            for count in self.suit_distribution() {
+                match count {
+                    4 => draws.flush_draws += 1,
+                    3 if board_size == 3 => draws.backdoor_flush_draws += 1,
+                    _ => {}
+                }
+            }
+        }
+
+        if !self.has_straight() {
+            let rankset = with_low_ace(self.rankset());
+            // bits of the missing ranks that complete a straight, in the same layout as `rankset`
+            let mut completing = 0;
+            for low in 0..10 {
+                let window = (rankset >> low) & 0x1f;
+                match window.count_ones() {
+                    4 => completing |= (!window & 0x1f) << low,
+                    3 if board_size == 3 => draws.backdoor_straight_draws += 1,
+                    _ => {}
+                }
+            }
+            // runs of 4 consecutive ranks whose adjacent ranks both exist (and thus are missing)
+            draws.oesd_draws = (1..10)
+                .filter(|low| (rankset >> low) & 0b1111 == 0b1111)
+                .count() as u8;
+            // merge the low ace into the high ace
+            let completing = (completing >> 1) | ((completing & 1) << 12);
+            draws.gutshot_draws =
+                (completing.count_ones() as u8).saturating_sub(2 * draws.oesd_draws);
+        }
+
+        draws
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn texture(board_str: &str) -> BoardTexture {
+        board_str.parse::<Hand>().unwrap().texture()
+    }
+
+    #[test]
+    fn test_count_draws() {
+        let draws = |hand_str: &str, board_size: usize| {
+            let draws = hand_str.parse::<Hand>().unwrap().count_draws(board_size);
+            [
+                draws.flush_draws,
+                draws.oesd_draws,
+                draws.gutshot_draws,
+                draws.backdoor_flush_draws,
+                draws.backdoor_straight_draws,
+            ]
+        };
+
+        // flush draw and open-ended straight draw
+        assert_eq!(draws("8s7s6s5dKs", 3), [1, 1, 0, 0, 2]);
+        assert_eq!(draws("8s7s6s5dKs2c", 4), [1, 1, 0, 0, 0]);
+        assert_eq!(draws("8s7s6s5dKs2c3h", 5), [0; 5]);
+
+        // gutshots, including one-ended draws and double gutshots
+        assert_eq!(draws("9h7d6c5sKs", 3), [0, 0, 1, 0, 3]);
+        assert_eq!(draws("As2d3c4hKs", 3), [0, 0, 1, 0, 1]);
+        assert_eq!(draws("AsKdQcJh2s", 3), [0, 0, 1, 0, 1]);
+        assert_eq!(draws("9h7d6c5s3s2c", 4), [0, 0, 2, 0, 0]);
+        assert_eq!(draws("2s3d4c5hKs", 3), [0, 1, 0, 0, 1]);
+
+        // backdoor draws
+        assert_eq!(draws("AsKs2s7d9h", 3), [0, 0, 0, 1, 0]);
+        assert_eq!(draws("QhJhTc2h7d", 3), [0, 0, 0, 1, 4]);
+
+        // made hands do not count as draws
+        assert_eq!(draws("8s7s6s5s4s", 3), [0; 5]);
+        assert_eq!(draws("9h8d7c6s5sAs", 4), [0; 5]);
+    }
+
+    #[test]
+    fn test_texture() {
+        let broadway = texture("AsKhQd");
+        assert!(broadway.rainbow && !broadway.two_tone && !broadway.monotone);
+        assert!(!broadway.paired && !broadway.has_flush_draw);
+        assert!(broadway.has_straight_draw && !broadway.wet);
+        assert_eq!(broadway.connectivity_score, 6);
+
+        let connectors = texture("9s8s7h");
+        assert!(connectors.two_tone && !connectors.rainbow && !connectors.monotone);
+        assert!(connectors.has_straight_draw && connectors.has_flush_draw);
+        assert!(connectors.wet);
+        assert_eq!(connectors.connectivity_score, 6);
+        assert!(texture("9s8d7h").wet);
+
+        let monotone = texture("Kh7h2h");
+        assert!(monotone.monotone && !monotone.two_tone && !monotone.rainbow);
+        assert!(monotone.has_flush_draw && !monotone.has_straight_draw);
+        assert!(monotone.wet);
+        assert_eq!(monotone.connectivity_score, 0);
+
+        let dry = texture("Ks7d7c");
+        assert!(dry.paired && dry.rainbow && !dry.has_straight_draw && !dry.wet);
+        assert_eq!(dry.connectivity_score, 0);
+        assert!(!texture("Ks7d2c").has_straight_draw);
+        assert!(texture("KsJd2c").has_straight_draw);
+
+        // the score covers 0-10
+        for (board, score) in &[
+            ("Ks7d2c", 0),
+            ("Ks9d2c", 1),
+            ("KsTd2c", 2),
+            ("KsJd2c", 3),
+            ("KsQd2c", 4),
+            ("KsJd9c", 4),
+            ("KsQdTc", 5),
+            ("As2d3c", 6),
+            ("AsKhQd", 6),
+            ("9s8d6c5h", 7),
+            ("9s8d7c6h", 8),
+            ("9s8s7hTsJd", 10),
+            ("AsKsQsJsTs", 10),
+        ] {
+            assert_eq!(texture(board).connectivity_score, *score, "{}", board);
+        }
+    }
+}
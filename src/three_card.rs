@@ -0,0 +1,131 @@
+use crate::hand::*;
+
+/// Hand categories of Three Card Poker, in ascending order of strength.
+/// Unlike the standard ranking, a straight beats a flush and three of a kind beats a straight.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum ThreeCardCategory {
+    HighCard = 0,
+    Pair = 1,
+    Flush = 2,
+    Straight = 3,
+    ThreeOfAKind = 4,
+    StraightFlush = 5,
+}
+
+/// Returns the Three Card Poker hand category from hand rank computed by `Hand::evaluate_3card()`.
+#[inline]
+pub fn get_three_card_category(hand_rank: u16) -> ThreeCardCategory {
+    match hand_rank >> 12 {
+        0 => ThreeCardCategory::HighCard,
+        1 => ThreeCardCategory::Pair,
+        2 => ThreeCardCategory::Flush,
+        3 => ThreeCardCategory::Straight,
+        4 => ThreeCardCategory::ThreeOfAKind,
+        5 => ThreeCardCategory::StraightFlush,
+        _ => unreachable!(),
+    }
+}
+
+/// rank set of A-2-3, the lowest straight
+const LOW_STRAIGHT: u64 = 0b1_0000_0000_0011;
+
+impl Hand {
+    /// Returns hand strength of 3-card hand `self` in 16-bit integer under the Three Card Poker
+    /// ranking (stronger hand yields higher value).
+    /// The upper 4 bits hold the `ThreeCardCategory`, and A-2-3 is the lowest straight.
+    /// This function panics when `self.len() != 3`.
+    pub fn evaluate_3card(&self) -> u16 {
+        assert_eq!(self.len(), 3);
+        let mask = self.get_mask();
+        let rankset = self.rankset();
+        let is_flush = (0..4).any(|suit| ((mask >> (16 * suit)) & 0x1fff).count_ones() == 3);
+        let top = 63 - rankset.leading_zeros() as u16;
+
+        let (category, index) = if rankset.count_ones() == 1 {
+            (ThreeCardCategory::ThreeOfAKind, top)
+        } else if rankset.count_ones() == 2 {
+            let pair = (0..13).find(|rank| self.count_rank(*rank) == 2).unwrap();
+            let kicker = (rankset & !(1 << pair)).trailing_zeros() as u16;
+            (ThreeCardCategory::Pair, pair as u16 * 13 + kicker)
+        } else {
+            let straight_top = if rankset == LOW_STRAIGHT {
+                Some(1)
+            } else if rankset == (0b111 << (top - 2)) {
+                Some(top)
+            } else {
+                None
+            };
+            match (straight_top, is_flush) {
+                (Some(top), true) => (ThreeCardCategory::StraightFlush, top),
+                (Some(top), false) => (ThreeCardCategory::Straight, top),
+                (None, flush) => {
+                    let ranks = (0..13)
+                        .rev()
+                        .filter(|rank| (rankset >> rank) & 1 != 0)
+                        .collect::<Vec<_>>();
+                    let category = if flush {
+                        ThreeCardCategory::Flush
+                    } else {
+                        ThreeCardCategory::HighCard
+                    };
+                    (category, combination_index(&ranks))
+                }
+            }
+        };
+        ((category as u16) << 12) | index
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card::*;
+    use assets::constants::*;
+    use std::collections::HashSet;
+    use std::convert::TryFrom;
+
+    fn evaluate(hand_str: &str) -> u16 {
+        hand_str.parse::<Hand>().unwrap().evaluate_3card()
+    }
+
+    #[test]
+    fn test_all_3card_combinations() {
+        let card = |i: usize| Card::try_from(i).unwrap();
+        let mut rankset = HashSet::new();
+        let mut counter = [0; ThreeCardCategory::StraightFlush as usize + 1];
+        for i in 0..(NUMBER_OF_CARDS - 2) {
+            for j in (i + 1)..(NUMBER_OF_CARDS - 1) {
+                for k in (j + 1)..NUMBER_OF_CARDS {
+                    let rank = Hand::from_slice(&[card(i), card(j), card(k)]).evaluate_3card();
+                    rankset.insert(rank);
+                    counter[get_three_card_category(rank) as usize] += 1;
+                }
+            }
+        }
+
+        assert_eq!(counter.iter().sum::<usize>(), 22100);
+        assert_eq!(counter[ThreeCardCategory::StraightFlush as usize], 48);
+        assert_eq!(counter[ThreeCardCategory::ThreeOfAKind as usize], 52);
+        assert_eq!(counter[ThreeCardCategory::Straight as usize], 720);
+        assert_eq!(counter[ThreeCardCategory::Flush as usize], 1096);
+        assert_eq!(counter[ThreeCardCategory::Pair as usize], 3744);
+        assert_eq!(counter[ThreeCardCategory::HighCard as usize], 16440);
+        // 12 + 13 + 12 + 274 + 156 + 274
+        assert_eq!(rankset.len(), 741);
+    }
+
+    #[test]
+    fn test_ordering() {
+        assert!(evaluate("AsKsQs") > evaluate("AsAhAc"));
+        assert!(evaluate("2s2h2c") > evaluate("AsKhQc"));
+        assert!(evaluate("As2h3c") > evaluate("AsKs9s"));
+        assert!(evaluate("2s3h4c") > evaluate("As2h3c"));
+        assert!(evaluate("As2s3s") < evaluate("2s3s4s"));
+        assert!(evaluate("2s4s6s") > evaluate("AsAhKc"));
+        assert!(evaluate("AsAh2c") > evaluate("KsKhAc"));
+        assert!(evaluate("KsKhAc") > evaluate("KsKhQc"));
+        assert!(evaluate("2s2h3c") > evaluate("AsKhJc"));
+        assert!(evaluate("AsKhJc") > evaluate("AsQhJc"));
+        assert_eq!(evaluate("AsKhJc"), evaluate("AhKdJs"));
+    }
+}